@@ -0,0 +1,81 @@
+//! Generates the tables of embedded data files for the `embed-inputs` feature, one per day.
+//! Without the feature, nothing is generated and the data directory is read at runtime.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    let data_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("data");
+    println!(
+        "cargo:rerun-if-changed={}",
+        data_dir.join("inputs").display()
+    );
+    println!(
+        "cargo:rerun-if-changed={}",
+        data_dir.join("examples").display()
+    );
+
+    let mut files = vec![];
+    collect_files(&data_dir, &data_dir.join("inputs"), &mut files);
+    collect_files(&data_dir, &data_dir.join("examples"), &mut files);
+    files.sort();
+
+    // One table per day, so that each solution only embeds its own files. Files are named after
+    // their day, such as `inputs/08.txt`, `inputs/08/alice.txt` or `examples/08-2.txt`.
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    for day in 1..=25 {
+        let prefix = format!("{day:02}");
+        let entries: String = files
+            .iter()
+            .filter(|(relative, _)| {
+                relative
+                    .split('/')
+                    .nth(1)
+                    .is_some_and(|name| name.starts_with(&prefix))
+            })
+            .map(|(relative, absolute)| {
+                format!("    ({relative:?}, include_str!({absolute:?})),\n")
+            })
+            .collect();
+
+        fs::write(
+            out_dir.join(format!("embedded_{day}.rs")),
+            format!("&[\n{entries}]\n"),
+        )
+        .unwrap();
+    }
+}
+
+/// Collect `.txt` and `.answers` files of `dir` and its sub-directories as pairs of their path
+/// relative to the data directory (with `/` separators) and their absolute path.
+fn collect_files(data_dir: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            println!("cargo:rerun-if-changed={}", path.display());
+            collect_files(data_dir, &path, files);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "txt" || ext == "answers")
+        {
+            let relative = path
+                .strip_prefix(data_dir)
+                .unwrap()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative, path));
+        }
+    }
+}
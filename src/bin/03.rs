@@ -12,15 +12,19 @@ where
 {
     let re = Regex::new(r"(\d+)").unwrap();
     let lines = input.trim().split('\n').collect_vec();
-    for row in 0..lines.len() {
-        let line = lines[row];
+    for (row, line) in lines.iter().enumerate() {
         // Get all the numbers in the line
         for cap in re.captures_iter(line) {
             let v = cap.get(1).unwrap();
             // Search the box surrounding the number, with bounds checking
-            for y in row.checked_sub(1).unwrap_or(0)..min(row + 2, lines.len()) {
-                for x in v.start().checked_sub(1).unwrap_or(0)..min(v.end() + 1, line.len()) {
-                    let c = lines[y].as_bytes()[x];
+            for (y, adjacent) in lines
+                .iter()
+                .enumerate()
+                .take(row + 2)
+                .skip(row.saturating_sub(1))
+            {
+                for x in v.start().saturating_sub(1)..min(v.end() + 1, line.len()) {
+                    let c = adjacent.as_bytes()[x];
                     process(c, (x, y), v.as_str(), (v.start(), row));
                 }
             }
@@ -30,9 +34,9 @@ where
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut parts = FxHashMap::default();
-    read_engine_schema(input, |c, _, part_num, part_loc| {
+    read_engine_schema(input, |c: u8, _, part_num, part_loc| {
         // if the coordinate is a symbol (in this case, not a . or 0-9), add it to the list of parts
-        if c != b'.' && (c < b'0' || c > b'9') {
+        if c != b'.' && !c.is_ascii_digit() {
             // add to hashmap with a key for that coordinate to avoid duplicates on that number
             parts.insert(part_loc, part_num.parse().unwrap());
         }
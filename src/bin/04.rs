@@ -1,4 +1,5 @@
-use itertools::Itertools;
+use advent_of_code::error::Result;
+use advent_of_code::parse::{self, ParseError};
 use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(4);
@@ -20,37 +21,25 @@ fn count_winning_numbers(winning_numbers: Vec<i32>, card_numbers: Vec<i32>) -> u
         .count() as u32
 }
 
-fn parse_card(line: &str) -> (Vec<i32>, Vec<i32>) {
-    let (winning, card) = line.split(':').collect_vec()[1]
-        .split('|')
-        .collect_tuple()
-        .unwrap();
-    (
-        winning
-            .split_ascii_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect_vec(),
-        card.split_ascii_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect_vec(),
-    )
+type Card = (Vec<i32>, Vec<i32>);
+
+fn parse_card(line: &str) -> std::result::Result<Card, ParseError> {
+    let expected = |what| ParseError::at_offset(line, line.len(), format!("expected {what}"));
+    let (_, numbers) = line.split_once(':').ok_or_else(|| expected("\":\""))?;
+    let (winning, card) = numbers.split_once('|').ok_or_else(|| expected("\"|\""))?;
+    let parse_numbers = |text| parse::numbers(text).map_err(|e| e.within(line, text));
+    Ok((parse_numbers(winning)?, parse_numbers(card)?))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32> {
     // Calculate the score for each card and then sum the total
-    Some(
-        input
-            .trim()
-            .split('\n')
-            .map(|line| {
-                let (winning_numbers, card_numbers) = parse_card(line);
-                score_card(winning_numbers, card_numbers)
-            })
-            .sum(),
-    )
+    Ok(parse::lines(input.trim(), parse_card)?
+        .into_iter()
+        .map(|(winning_numbers, card_numbers)| score_card(winning_numbers, card_numbers))
+        .sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32> {
     // Simplifying assumptions
     // - input cards are in order
     // - no cards extend beyond the list (per instructions)
@@ -59,7 +48,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     // (alternatively could use a vector with pre-determined size and index them since it's ordered and not sparse)
     let mut card_count = FxHashMap::default();
 
-    for (i, card) in input.trim().split('\n').enumerate() {
+    let cards = parse::lines(input.trim(), parse_card)?;
+    for (i, (winning_numbers, card_numbers)) in cards.into_iter().enumerate() {
         let card_num = i + 1;
 
         // Get the number of copies we already have of this card, including the original
@@ -68,7 +58,6 @@ pub fn part_two(input: &str) -> Option<u32> {
             .and_modify(|v| *v += 1)
             .or_insert(1);
 
-        let (winning_numbers, card_numbers) = parse_card(card);
         let c = count_winning_numbers(winning_numbers, card_numbers) as usize;
         // For each of the following cards (up to the limit of winning numbers),
         // add one for each copy we have
@@ -80,7 +69,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(card_count.values().sum())
+    Ok(card_count.values().sum())
 }
 
 #[cfg(test)]
@@ -109,12 +98,20 @@ mod tests {
         );
 
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result, Ok(13));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result, Ok(30));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = part_one("Card 1: 1 2 | 3\nCard 2: 4 x | 5\n").unwrap_err();
+        let location = error.location.unwrap();
+        assert_eq!((location.line, location.column), (2, 11));
+        assert_eq!(location.source_line, "Card 2: 4 x | 5");
     }
 }
@@ -17,7 +17,7 @@ fn find_num_records(time: u64, record: u64) -> u64 {
     // The number of records will be the total time, less twice the number of non-records.
 
     let mut l = 0;
-    let mut r = (time + 1) / 2;
+    let mut r = time.div_ceil(2);
 
     while l != r {
         if r - l == 1 {
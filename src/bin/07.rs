@@ -27,7 +27,7 @@ impl Hand {
         let rank = calc_rank(s);
 
         Self {
-            value: String::from(s).chars().map(|c| card_value(c)).collect_vec(),
+            value: String::from(s).chars().map(card_value).collect_vec(),
             rank,
         }
     }
@@ -90,22 +90,22 @@ fn calc_rank(s: &str) -> HandCount {
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(&other).unwrap()
-    }
-}
-
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         // Compare hands by rank
         let result = self.rank.partial_cmp(&other.rank).unwrap();
         if result == Ordering::Equal {
-            Some(self.value.cmp(&other.value))
+            self.value.cmp(&other.value)
         } else {
-            Some(result)
+            result
         }
     }
 }
 
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl PartialEq for Hand {
     // Compare hands by rank
     fn eq(&self, other: &Self) -> bool {
@@ -129,11 +129,10 @@ fn calculate_result(input: &str, with_joker: bool) -> usize {
         });
 
     // multiply rank (i + 1) by the bid for each hand, then return the sum
-    let result = bids
-        .enumerate()
+
+    bids.enumerate()
         .map(|(i, bid)| (i + 1) * bid[1].parse::<usize>().unwrap())
-        .sum();
-    result
+        .sum()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -1,18 +1,22 @@
+use advent_of_code::cycle::find_cycle_by_key;
+use advent_of_code::error::{Error, Result};
+use advent_of_code::number_theory::first_coincidence;
 use aoc_parse::{parser, prelude::*};
 use itertools::Itertools;
-use num::Integer;
 use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(8);
 
-fn parse_maps(input: &str) -> (Vec<usize>, FxHashMap<String, [String; 2]>) {
+type Maps = FxHashMap<String, [String; 2]>;
+
+fn parse_maps(input: &str) -> Result<(Vec<usize>, Maps)> {
     let p = parser!(
         line(char_of("LR")+)
         line("")
         lines(string(alnum+) " = (" string(alnum+) ", " string(alnum+) ")")
     );
 
-    let (instructions, _, maps_definition) = p.parse(input).unwrap();
+    let (instructions, _, maps_definition) = p.parse(input)?;
 
     let mut maps = FxHashMap::default();
     for map in maps_definition {
@@ -20,14 +24,10 @@ fn parse_maps(input: &str) -> (Vec<usize>, FxHashMap<String, [String; 2]>) {
     }
 
     // instructions is a vector of 0 = L, 1 = R; maps is src => dest(L, R)
-    (instructions, maps)
+    Ok((instructions, maps))
 }
 
-fn traverse_map(
-    start: &str,
-    instructions: &Vec<usize>,
-    maps: &FxHashMap<String, [String; 2]>,
-) -> usize {
+fn traverse_map(start: &str, instructions: &[usize], maps: &Maps) -> usize {
     // calculate the distance from the start to a destination
     let mut pos = start;
     let mut count = 0;
@@ -40,30 +40,106 @@ fn traverse_map(
     count
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (instructions, maps) = parse_maps(input);
+/// The steps at which a route is at a destination: some only once before the route enters its
+/// cycle, and the others again every period after that.
+struct Arrivals {
+    once: Vec<usize>,
+    repeating: Vec<usize>,
+    period: usize,
+}
+
+impl Arrivals {
+    fn contains(&self, step: usize) -> bool {
+        self.once.contains(&step)
+            || self
+                .repeating
+                .iter()
+                .any(|&first| step >= first && (step - first).is_multiple_of(self.period))
+    }
+}
+
+fn find_arrivals(start: &str, instructions: &[usize], maps: &Maps) -> Arrivals {
+    // The route repeats once it is back at the same node at the same point of the instructions
+    let history = find_cycle_by_key(
+        (start, 0),
+        |&(pos, count)| {
+            (
+                maps[pos][instructions[count % instructions.len()]].as_str(),
+                count + 1,
+            )
+        },
+        |&(pos, count)| (pos, count % instructions.len()),
+    );
+
+    let (once, repeating) = history
+        .states()
+        .iter()
+        .filter(|(pos, count)| *count > 0 && pos.ends_with('Z'))
+        .map(|(_, count)| *count)
+        .partition(|count| *count < history.cycle.start);
+
+    Arrivals {
+        once,
+        repeating,
+        period: history.cycle.length,
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let (instructions, maps) = parse_maps(input)?;
+    if !maps.contains_key("AAA") {
+        return Err(Error::new("no node named \"AAA\" to start from"));
+    }
 
     let count = traverse_map("AAA", &instructions, &maps);
 
-    Some(count)
+    Ok(count)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (instructions, maps) = parse_maps(input);
+pub fn part_two(input: &str) -> Result<usize> {
+    let (instructions, maps) = parse_maps(input)?;
+
+    // For each possible start point, find the steps at which it reaches a destination
+    let routes = maps
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .map(|start| find_arrivals(start, &instructions, &maps))
+        .collect_vec();
+    if routes.is_empty() {
+        return Err(Error::new("no nodes ending in 'A' to start from"));
+    }
 
-    // calculate the distance to the destination of each possible start point
-    let starts = maps.keys().filter(|k| k.ends_with('A')).collect_vec();
-    let counts = starts
+    // A destination reached before its route repeats must be checked against every other route
+    let early = routes
         .iter()
-        .map(|start| traverse_map(start, &instructions, &maps));
+        .flat_map(|route| route.once.iter().copied())
+        .filter(|&step| routes.iter().all(|route| route.contains(step)))
+        .min();
 
-    // Calculate the least common multiple of the routes - this will be the point in time that each
-    // of the routes land at a destination at the same time.
+    // Find the first point in time that each of the routes land at a destination at the same time,
+    // for every choice of a destination within the cycle of each route.
     //
-    // This works because of an assumption in the input data - it is structed such that continuing
-    // to traverse past the destination will reach the same destination again in the same distance
-    // as the original route.
-    counts.reduce(|acc, e| acc.lcm(&e))
+    // In the input data, each cycle holds a single destination that is reached again in the same
+    // distance as the original route, making this the least common multiple of the routes.
+    let repeating = routes
+        .iter()
+        .map(|route| {
+            route
+                .repeating
+                .iter()
+                .map(|&first| (first as i64, route.period as i64))
+                .collect_vec()
+        })
+        .multi_cartesian_product()
+        .filter_map(first_coincidence)
+        .map(|step| step as usize)
+        .min();
+
+    early
+        .into_iter()
+        .chain(repeating)
+        .min()
+        .ok_or_else(|| Error::new("the routes never arrive at the same time"))
 }
 
 #[cfg(test)]
@@ -75,12 +151,12 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file_part(
             "examples", DAY, 11,
         ));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
 
         let result = part_one(&advent_of_code::template::read_file_part(
             "examples", DAY, 12,
         ));
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Ok(6));
     }
 
     #[test]
@@ -88,6 +164,22 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 21,
         ));
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_arrivals_out_of_phase() {
+        // 11A reaches 11Z after 1 step, and then after 4, 6, 8, ... steps, so the gap between the
+        // first two arrivals isn't its period. 22A reaches 22Z after 6, 8, 10, ... steps
+        let input = "LR\n\n11A = (11Z, XXX)\n11Z = (11B, 11B)\n11B = (11B, 11Z)\n\
+                     22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22D, 22D)\n22D = (22E, 22E)\n\
+                     22E = (22F, 22F)\n22F = (22Z, 22Z)\n22Z = (22G, 22G)\n22G = (22Z, 22Z)\n\
+                     XXX = (XXX, XXX)\n";
+        assert_eq!(part_two(input), Ok(6));
+
+        // 11A only reaches 11Z after odd steps, and 22A only reaches 22Z after even steps
+        let input = "L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11Z, 11Z)\n\
+                     22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)\n";
+        assert!(part_two(input).is_err());
     }
 }
@@ -1,50 +1,29 @@
+use advent_of_code::sequence::Polynomial;
 use aoc_parse::{parser, prelude::*};
 
 advent_of_code::solution!(9);
 
-fn solve_equation(equation: &[i32], backwards: bool) -> i32 {
-    // Solve recursively by generating the diffs and then solving again,
-    // finally returning the element to generate. This element is either
-    // added to the last element to append to the list and returned up,
-    // or subtracted from the first element to prepend to the list and
-    // returned up.
-
-    // If all the elements are 0 we are done and return the new 0
-    // element to help generate the next difference.
-    if equation.iter().all(|i| *i == 0) {
-        return 0;
-    }
-
-    let mut diffs = Vec::new();
-    for i in 1..equation.len() {
-        diffs.push(equation[i] - equation[i - 1]);
-    }
-
-    let result = solve_equation(&diffs, backwards);
-    if backwards {
-        equation.first().unwrap() - result
-    } else {
-        equation.last().unwrap() + result
-    }
-}
-
-fn solve(input: &str, backwards: bool) -> Option<i32> {
-    let p = parser!(lines(line(repeat_sep(i32, " "))));
+fn solve(input: &str, backwards: bool) -> Option<i64> {
+    let p = parser!(lines(line(repeat_sep(i64, " "))));
     let v = p.parse(input).unwrap();
 
-    // Solve for the new element to generate in each equation, and then add them together
-    Some(
-        v.iter()
-            .map(|equation| solve_equation(equation, backwards))
-            .sum(),
-    )
+    // Each history is generated by a polynomial, found by repeatedly taking differences. The new
+    // element is the value of that polynomial either just after the last element, or just before
+    // the first. Add them together.
+    v.iter()
+        .map(|history| {
+            let polynomial = Polynomial::from_sequence(history);
+            let index = if backwards { -1 } else { history.len() as i64 };
+            polynomial.eval_i64(index)
+        })
+        .sum()
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
+pub fn part_one(input: &str) -> Option<i64> {
     solve(input, false)
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
+pub fn part_two(input: &str) -> Option<i64> {
     solve(input, true)
 }
 
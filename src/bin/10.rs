@@ -1,9 +1,9 @@
+use advent_of_code::geometry::{Direction, Point};
+use advent_of_code::polygon::Polygon;
 use aoc_parse::{parser, prelude::*};
 use itertools::Itertools;
 use num::FromPrimitive;
-
-#[macro_use]
-extern crate num_derive;
+use num_derive::FromPrimitive;
 
 advent_of_code::solution!(10);
 
@@ -19,15 +19,7 @@ enum Pipe {
     Start,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
-
-fn find_start(pipes: &Vec<Vec<Pipe>>) -> (usize, usize) {
+fn find_start(pipes: &[Vec<Pipe>]) -> (usize, usize) {
     for (y, row) in pipes.iter().enumerate() {
         for (x, pipe) in row.iter().enumerate() {
             if *pipe == Pipe::Start {
@@ -38,7 +30,7 @@ fn find_start(pipes: &Vec<Vec<Pipe>>) -> (usize, usize) {
     panic!("Didn't find start");
 }
 
-fn find_pipe_from_start(pipes: &Vec<Vec<Pipe>>, start_x: usize, start_y: usize) -> Direction {
+fn find_pipe_from_start(pipes: &[Vec<Pipe>], start_x: usize, start_y: usize) -> Direction {
     // We're not told what type of pipe the start is, so look at the surrounding pipes
     // to determine what the valid connections are.
 
@@ -48,33 +40,33 @@ fn find_pipe_from_start(pipes: &Vec<Vec<Pipe>>, start_x: usize, start_y: usize)
     if start_y > 0 {
         let pipe = &pipes[start_y - 1][start_x];
         if *pipe == Pipe::NS || *pipe == Pipe::SW || *pipe == Pipe::SE {
-            directions.push(Direction::N);
+            directions.push(Direction::Up);
         }
     }
     // Check south
     if start_y < pipes.len() - 1 {
         let pipe = &pipes[start_y + 1][start_x];
         if *pipe == Pipe::NS || *pipe == Pipe::NW || *pipe == Pipe::NE {
-            directions.push(Direction::S);
+            directions.push(Direction::Down);
         }
     }
     // Check east
     if start_x < pipes[0].len() - 1 {
         let pipe = &pipes[start_y][start_x + 1];
         if *pipe == Pipe::NW || *pipe == Pipe::SW || *pipe == Pipe::EW {
-            directions.push(Direction::E);
+            directions.push(Direction::Right);
         }
     }
     // Check west
     if start_x > 0 {
         let pipe = &pipes[start_y][start_x - 1];
         if *pipe == Pipe::NE || *pipe == Pipe::SE || *pipe == Pipe::EW {
-            directions.push(Direction::W);
+            directions.push(Direction::Left);
         }
     }
 
     // Return the pipe type, but also one of the valid directions to use as a starting direction
-    directions.first().unwrap().clone()
+    *directions.first().unwrap()
 }
 
 fn pipe_direction(direction: &Direction, pipe: &Pipe) -> Direction {
@@ -82,52 +74,52 @@ fn pipe_direction(direction: &Direction, pipe: &Pipe) -> Direction {
     // what the next direction will be based on the pipe type
     match pipe {
         Pipe::NS => {
-            if *direction == Direction::N {
-                Direction::N
+            if *direction == Direction::Up {
+                Direction::Up
             } else {
-                Direction::S
+                Direction::Down
             }
         }
         Pipe::EW => {
-            if *direction == Direction::E {
-                Direction::E
+            if *direction == Direction::Right {
+                Direction::Right
             } else {
-                Direction::W
+                Direction::Left
             }
         }
         Pipe::NE => {
-            if *direction == Direction::S {
-                Direction::E
+            if *direction == Direction::Down {
+                Direction::Right
             } else {
-                Direction::N
+                Direction::Up
             }
         }
         Pipe::NW => {
-            if *direction == Direction::S {
-                Direction::W
+            if *direction == Direction::Down {
+                Direction::Left
             } else {
-                Direction::N
+                Direction::Up
             }
         }
         Pipe::SW => {
-            if *direction == Direction::N {
-                Direction::W
+            if *direction == Direction::Up {
+                Direction::Left
             } else {
-                Direction::S
+                Direction::Down
             }
         }
         Pipe::SE => {
-            if *direction == Direction::N {
-                Direction::E
+            if *direction == Direction::Up {
+                Direction::Right
             } else {
-                Direction::S
+                Direction::Down
             }
         }
         _ => panic!("Should not hit unconnected pipe"),
     }
 }
 
-fn traverse_map(input: &str) -> (i32, usize) {
+fn traverse_map(input: &str) -> Polygon {
     let p = parser!(lines(
         line:char_of("|-LJ7F.S")+ => line.into_iter().map(|pipe| -> Pipe { FromPrimitive::from_usize(pipe).unwrap()} ).collect_vec()
     ));
@@ -141,45 +133,37 @@ fn traverse_map(input: &str) -> (i32, usize) {
     let mut direction = find_pipe_from_start(&pipes, start_x, start_y);
 
     // Navigate the pipe from the starting point until we return
-    // to the starting point, calling process closure on each location
+    // to the starting point, recording each location as a vertex of the loop
     let start_pos = (start_x as isize, start_y as isize);
     let mut pos = start_pos;
-    let mut perimeter = 0;
-    let mut area = 0;
+    let mut vertices = vec![];
     loop {
-        perimeter += 1;
-        let new_pos = match &direction {
-            Direction::N => (pos.0, pos.1 - 1),
-            Direction::S => (pos.0, pos.1 + 1),
-            Direction::E => (pos.0 + 1, pos.1),
-            Direction::W => (pos.0 - 1, pos.1),
-        };
+        vertices.push(Point::new(pos.0 as i64, pos.1 as i64));
+        let offset = direction.offset();
+        let new_pos = (pos.0 + offset.x as isize, pos.1 + offset.y as isize);
         if new_pos == start_pos {
             break;
         }
-        area += (new_pos.0 + pos.0) * (new_pos.1 - pos.1);
         pos = new_pos;
 
         let pipe = &pipes[pos.1 as usize][pos.0 as usize];
-        direction = pipe_direction(&direction, &pipe);
+        direction = pipe_direction(&direction, pipe);
     }
-    (perimeter, area.abs() as usize / 2)
+    Polygon::from_vertices(vertices)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     // Traverse the main pipe and count the steps
-    let (perimeter, _) = traverse_map(input);
+    let pipe_loop = traverse_map(input);
 
     // Return half the steps, as this is the furthest point from the start
-    Some(perimeter as u32 / 2)
+    Some(pipe_loop.perimeter() as u32 / 2)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    // Traverse the main pipe and calculate the area and perimeter
-    let (perimeter, area) = traverse_map(input);
-    // The area measures from the middle of each block in the perimeter, so we remove half the perimeter from the
-    // calculated area to get the inside, then add 1 for the 4 outermost corners
-    return Some(area - perimeter as usize / 2 + 1);
+    // Traverse the main pipe and count the tiles enclosed by it. Each tile of the loop is a lattice
+    // point on the boundary, so the enclosed tiles are the interior points of the polygon
+    Some(traverse_map(input).interior_points() as usize)
 }
 
 #[cfg(test)]
@@ -1,45 +1,20 @@
-use aoc_parse::{parser, prelude::*};
+use advent_of_code::geometry::Point;
+use advent_of_code::grid::Grid;
 use itertools::Itertools;
 
 advent_of_code::solution!(11);
 
-#[derive(Debug, PartialEq)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl Point {
-    fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-}
-
-fn calculate_distance(start: &Point, end: &Point) -> isize {
-    // The distance between two points will be the total number of horizontal steps
-    // plus the total number of vertical steps
-    (end.x - start.x).abs() + (end.y - start.y).abs()
-}
-
 fn parse_map(input: &str) -> Vec<Point> {
-    let p = parser!(lines(
-        char_of(".#")+
-    ));
-    let v = p.parse(input).unwrap();
+    let grid = Grid::parse(input, |c| c == '#');
 
     // Create a sparse map of galaxies
-    let mut result = Vec::new();
-    for (y, row) in v.iter().enumerate() {
-        for (x, c) in row.iter().enumerate() {
-            if *c == 1 {
-                result.push(Point::new(x as isize, y as isize));
-            }
-        }
-    }
-    result
+    grid.iter()
+        .filter(|(_, galaxy)| **galaxy)
+        .map(|((x, y), _)| Point::new(x as i64, y as i64))
+        .collect()
 }
 
-fn expand_map(input: &Vec<Point>, expansion_rate: isize) -> Vec<Point> {
+fn expand_map(input: &[Point], expansion_rate: i64) -> Vec<Point> {
     let cols = input.iter().map(|p| p.x).collect_vec();
     let rows = input.iter().map(|p| p.y).collect_vec();
 
@@ -57,33 +32,33 @@ fn expand_map(input: &Vec<Point>, expansion_rate: isize) -> Vec<Point> {
         .iter()
         .map(|p| {
             Point::new(
-                p.x + expand_cols.iter().filter(|col| **col < p.x).count() as isize
+                p.x + expand_cols.iter().filter(|col| **col < p.x).count() as i64
                     * (expansion_rate - 1),
-                p.y + expand_rows.iter().filter(|row| **row < p.y).count() as isize
+                p.y + expand_rows.iter().filter(|row| **row < p.y).count() as i64
                     * (expansion_rate - 1),
             )
         })
         .collect_vec()
 }
 
-fn calculate_total_distance(map: Vec<Point>) -> Option<isize> {
+fn calculate_total_distance(map: Vec<Point>) -> Option<i64> {
     // Get all of the pairs of entries in the map, calculate the distance between them,
     // then sum the result
     let mut total = 0;
     for (i, el1) in map.iter().enumerate() {
         for el2 in &map[i + 1..] {
-            total += calculate_distance(&el1, &el2);
+            total += el1.manhattan(*el2);
         }
     }
     Some(total)
 }
 
-pub fn part_one(input: &str) -> Option<isize> {
+pub fn part_one(input: &str) -> Option<i64> {
     let map = expand_map(&parse_map(input), 2);
     calculate_total_distance(map)
 }
 
-pub fn part_two(input: &str) -> Option<isize> {
+pub fn part_two(input: &str) -> Option<i64> {
     let map = expand_map(&parse_map(input), 1000000);
     calculate_total_distance(map)
 }
@@ -93,20 +68,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_calculate_distance() {
-        assert_eq!(calculate_distance(&Point::new(1, 6), &Point::new(5, 11)), 9);
-        assert_eq!(
-            calculate_distance(&Point::new(4, 0), &Point::new(9, 10)),
-            15
-        );
-        assert_eq!(
-            calculate_distance(&Point::new(0, 2), &Point::new(12, 7)),
-            17
-        );
-        assert_eq!(
-            calculate_distance(&Point::new(0, 11), &Point::new(5, 11)),
-            5
-        );
+    fn test_manhattan_distance() {
+        assert_eq!(Point::new(1, 6).manhattan(Point::new(5, 11)), 9);
+        assert_eq!(Point::new(4, 0).manhattan(Point::new(9, 10)), 15);
+        assert_eq!(Point::new(0, 2).manhattan(Point::new(12, 7)), 17);
+        assert_eq!(Point::new(0, 11).manhattan(Point::new(5, 11)), 5);
     }
 
     #[test]
@@ -1,8 +1,8 @@
 use std::cmp::min;
 
+use advent_of_code::memo::Memo;
 use aoc_parse::{parser, prelude::*};
 use itertools::Itertools;
-use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(12);
 
@@ -10,18 +10,18 @@ const EMPTY: usize = 0;
 const SPRING: usize = 1;
 const WILD: usize = 2;
 
-fn find_arrangements(v: &Vec<usize>, sizes: &[i32]) -> i64 {
-    find_arrangements_internal(v, sizes, 0, &mut FxHashMap::default())
+fn find_arrangements(v: &[usize], sizes: &[i32]) -> i64 {
+    find_arrangements_internal(v, sizes, 0, &mut Memo::new())
 }
 
 fn find_arrangements_internal(
     v: &[usize],
     sizes: &[i32],
     min_space: usize,
-    memo: &mut FxHashMap<(usize, usize), i64>,
+    memo: &mut Memo<(usize, usize), i64>,
 ) -> i64 {
     // If we have processed all the springs, check the rest of the pattern is empty
-    if sizes.len() == 0 {
+    if sizes.is_empty() {
         if v[..].iter().all(|c| *c == EMPTY || *c == WILD) {
             // this is one single successful arrangement
             return 1;
@@ -31,45 +31,42 @@ fn find_arrangements_internal(
         }
     }
 
-    // Check if we've already processed the remainder before and reuse that calculation
+    // Reuse the calculation if we've already processed the remainder before
     // Key by remaining string length and remaining number of spring combinations to process
     // As we go left to right, we know these compose a complete right-hand side
-    let memo_key = (v.len(), sizes.len());
-    if let Some(result) = memo.get(&memo_key) {
-        return *result;
-    }
-
-    // Minimum that must be required to complete the remaining springs (total size of
-    // the springs, plus a gap of at least one in between)
-    let min_required = sizes.iter().sum::<i32>() as usize + sizes.len() - 1;
-    // The number of possible spaces at the start, which we can try combinations of
-    let extra_spaces_remaining = v.len() - min_required;
-    // Index of the first known spring, so we don't attempt any more spaces than that
-    let first_spring = v.iter().position(|i| *i == SPRING).unwrap_or(v.len());
-    let spaces_to_check = min(extra_spaces_remaining, first_spring);
-    // How many spring characters are required to make the next pattern valid
-    let expected_spring_size = sizes[0] as usize;
-
-    let mut arrangements = 0;
-    // min_space starts as 0, but will be 1 for all interleaving segments
-    // check if we have a valid next segment, for each possible number of extra spaces
-    // we can use at this point
-    for i in min_space..=spaces_to_check {
-        if v[0..i].iter().all(|c| *c == EMPTY || *c == WILD)
-            && v[i..i + expected_spring_size]
-                .iter()
-                .all(|c| *c == SPRING || *c == WILD)
-        {
-            // if the segment is valid, move on to the next segment of spaces and springs, then add the total found
-            arrangements +=
-                find_arrangements_internal(&v[i + expected_spring_size..], &sizes[1..], 1, memo);
+    memo.get_or_compute(&(v.len(), sizes.len()), |memo| {
+        // Minimum that must be required to complete the remaining springs (total size of
+        // the springs, plus a gap of at least one in between)
+        let min_required = sizes.iter().sum::<i32>() as usize + sizes.len() - 1;
+        // The number of possible spaces at the start, which we can try combinations of
+        let extra_spaces_remaining = v.len() - min_required;
+        // Index of the first known spring, so we don't attempt any more spaces than that
+        let first_spring = v.iter().position(|i| *i == SPRING).unwrap_or(v.len());
+        let spaces_to_check = min(extra_spaces_remaining, first_spring);
+        // How many spring characters are required to make the next pattern valid
+        let expected_spring_size = sizes[0] as usize;
+
+        let mut arrangements = 0;
+        // min_space starts as 0, but will be 1 for all interleaving segments
+        // check if we have a valid next segment, for each possible number of extra spaces
+        // we can use at this point
+        for i in min_space..=spaces_to_check {
+            if v[0..i].iter().all(|c| *c == EMPTY || *c == WILD)
+                && v[i..i + expected_spring_size]
+                    .iter()
+                    .all(|c| *c == SPRING || *c == WILD)
+            {
+                // if the segment is valid, move on to the next segment of spaces and springs, then add the total found
+                arrangements += find_arrangements_internal(
+                    &v[i + expected_spring_size..],
+                    &sizes[1..],
+                    1,
+                    memo,
+                );
+            }
         }
-    }
-
-    // memoize this result in case we need the calculation again to improve performace
-    memo.insert(memo_key, arrangements);
-
-    arrangements
+        arrangements
+    })
 }
 
 fn parse_map(input: &str) -> Vec<(Vec<usize>, Vec<i32>)> {
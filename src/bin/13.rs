@@ -29,12 +29,7 @@ fn find_mirror(section: Vec<usize>, error_target: usize) -> Option<u32> {
     None
 }
 
-fn check_range(
-    start: usize,
-    end: usize,
-    section: &Vec<usize>,
-    error_target: usize,
-) -> Option<usize> {
+fn check_range(start: usize, end: usize, section: &[usize], error_target: usize) -> Option<usize> {
     // Check all rows of start to end (inclusive) to ensure they are mirrored
 
     let mut errors = 0;
@@ -78,7 +73,7 @@ fn process(input: &str, error_target: usize) -> u32 {
 
     let mut current_section = Vec::new();
     for line in input.split("\n") {
-        if line == "" {
+        if line.is_empty() {
             v.push(current_section);
             current_section = Vec::new();
         } else {
@@ -106,8 +101,8 @@ fn process(input: &str, error_target: usize) -> u32 {
             .iter()
             .map(|row| {
                 let mut value = 0;
-                for i in 0..row.len() {
-                    value |= row[i] << i;
+                for (i, v) in row.iter().enumerate() {
+                    value |= v << i;
                 }
                 value
             })
@@ -133,7 +128,7 @@ fn process(input: &str, error_target: usize) -> u32 {
             total += result;
         }
     }
-    total as u32
+    total
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-
+use advent_of_code::cycle::find_cycle_in_place;
+use advent_of_code::geometry::Direction;
 use aoc_parse::{parser, prelude::*};
 use itertools::{Either, Itertools};
 use seahash::hash;
@@ -10,13 +10,6 @@ const EMPTY: u8 = 0;
 const SQUARE: u8 = 1;
 const ROUND: u8 = 2;
 
-enum Direction {
-    N,
-    W,
-    S,
-    E,
-}
-
 struct Grid {
     w: i32,
     h: i32,
@@ -28,22 +21,22 @@ impl Grid {
         // roll along grid height for N/S, grid width for E/W
         // the other axis is what we scan across to process for each roll step
         let (scan_length, roll_length) = match direction {
-            Direction::N | Direction::S => (self.w, self.h),
-            Direction::E | Direction::W => (self.h, self.w),
+            Direction::Up | Direction::Down => (self.w, self.h),
+            Direction::Right | Direction::Left => (self.h, self.w),
         };
 
         // We step along from the destination of the roll, so
         // N/W start at 0 and move positively, S/E start at end and move negavtively
         let (start_value, inc) = match direction {
-            Direction::N | Direction::W => (0, 1),
-            Direction::S | Direction::E => (roll_length - 1, -1),
+            Direction::Up | Direction::Left => (0, 1),
+            Direction::Down | Direction::Right => (roll_length - 1, -1),
         };
 
         // Find the index into the byte array based on the direction
         fn get_index(direction: &Direction, size: i32, scan_idx: i32, roll_idx: i32) -> usize {
             match direction {
-                Direction::N | Direction::S => (roll_idx * size + scan_idx) as usize,
-                Direction::E | Direction::W => (scan_idx * size + roll_idx) as usize,
+                Direction::Up | Direction::Down => (roll_idx * size + scan_idx) as usize,
+                Direction::Right | Direction::Left => (scan_idx * size + roll_idx) as usize,
             }
         }
 
@@ -80,7 +73,7 @@ impl Grid {
                             last_free[scan_idx as usize],
                         )] = ROUND;
                         self.grid[grid_idx] = EMPTY;
-                        last_free[scan_idx as usize] = last_free[scan_idx as usize] + inc;
+                        last_free[scan_idx as usize] += inc;
                     } else {
                         last_free[scan_idx as usize] = roll_idx + inc;
                     }
@@ -141,37 +134,27 @@ impl Grid {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut grid = Grid::parse_grid(input);
-    grid.rock_and_roll(Direction::N);
+    grid.rock_and_roll(Direction::Up);
     Some(grid.calculate_load() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut grid = Grid::parse_grid(input);
-    let mut seen = HashMap::new();
-
-    let mut target = 1000000000;
-    let mut num = 0;
-    while num < target {
-        // hash the current grid as a byte array and store in a hashmap
-        let k = hash(grid.grid.as_slice());
-        if let Some(prev) = seen.insert(k, num) {
-            // If we found one that was previously encountered, there is a cycle
-            // Measure the interval of the cycle
-            let interval = num - prev;
-            // Find how many iterations would be left to do if we repeated this
-            // cycle to the last one before the target
-            let remaining = (target - num) % interval;
-            // set the target to the same point within the current cycle to short circuit
-            target = num + remaining;
-        }
 
-        grid.rock_and_roll(Direction::N);
-        grid.rock_and_roll(Direction::W);
-        grid.rock_and_roll(Direction::S);
-        grid.rock_and_roll(Direction::E);
+    fn spin(grid: &mut Grid) {
+        grid.rock_and_roll(Direction::Up);
+        grid.rock_and_roll(Direction::Left);
+        grid.rock_and_roll(Direction::Down);
+        grid.rock_and_roll(Direction::Right);
+    }
 
-        num += 1;
+    // Run spin cycles until the grid repeats, comparing grids by a hash of their bytes, then
+    // finish the spins from the first repetition to the same point within the cycle as the target
+    let cycle = find_cycle_in_place(&mut grid, spin, |grid| hash(grid.grid.as_slice()));
+    for _ in cycle.start..cycle.equivalent_iteration(1000000000) {
+        spin(&mut grid);
     }
+
     Some(grid.calculate_load() as u32)
 }
 
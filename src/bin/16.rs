@@ -1,3 +1,4 @@
+use advent_of_code::geometry::Direction;
 use aoc_parse::{parser, prelude::*};
 
 advent_of_code::solution!(16);
@@ -5,26 +6,17 @@ advent_of_code::solution!(16);
 struct Beam {
     x: i32,
     y: i32,
-    direction: i32,
+    direction: Direction,
 }
 
 impl Beam {
     fn move_beam(&mut self) {
-        match self.direction {
-            UP => self.y -= 1,
-            RIGHT => self.x += 1,
-            DOWN => self.y += 1,
-            LEFT => self.x -= 1,
-            _ => panic!("Invalid direction {}", self.direction),
-        }
+        let offset = self.direction.offset();
+        self.x += offset.x as i32;
+        self.y += offset.y as i32;
     }
 }
 
-const UP: i32 = 0;
-const RIGHT: i32 = 1;
-const LEFT: i32 = 2;
-const DOWN: i32 = 3;
-
 const EMPTY: usize = 0;
 const V_SPLIT: usize = 1;
 const H_SPLIT: usize = 2;
@@ -38,7 +30,7 @@ fn parse_grid(input: &str) -> Vec<Vec<usize>> {
     p.parse(input).unwrap()
 }
 
-fn count_energised_tiles(grid: &Vec<Vec<usize>>, beam: Beam) -> u32 {
+fn count_energised_tiles(grid: &[Vec<usize>], beam: Beam) -> u32 {
     let mut light_grid = [[0; 120]; 120];
 
     let (w, h) = (grid[0].len() as i32, grid.len() as i32);
@@ -54,7 +46,7 @@ fn count_energised_tiles(grid: &Vec<Vec<usize>>, beam: Beam) -> u32 {
 
             // If we have encountered this grid square with the same source direction,
             // mark this beam as done
-            let mask = 1 << beam.direction;
+            let mask = 1 << beam.direction as u8;
             if light_grid[beam.y as usize][beam.x as usize] & mask != 0 {
                 break;
             }
@@ -68,58 +60,54 @@ fn count_energised_tiles(grid: &Vec<Vec<usize>>, beam: Beam) -> u32 {
                 }
                 V_SPLIT => {
                     match beam.direction {
-                        UP | DOWN => {
+                        Direction::Up | Direction::Down => {
                             // treat as empty - will move the light beam forward
                         }
-                        LEFT | RIGHT => {
+                        Direction::Left | Direction::Right => {
                             // split the beam, this one continues up, and create a new one going down
-                            beam.direction = UP;
+                            beam.direction = Direction::Up;
                             let mut new_beam = Beam {
-                                direction: DOWN,
+                                direction: Direction::Down,
                                 ..beam
                             };
                             new_beam.move_beam();
                             beams.push(new_beam);
                         }
-                        _ => panic!("invalid direction"),
                     }
                 }
                 H_SPLIT => {
                     match beam.direction {
-                        LEFT | RIGHT => {
+                        Direction::Left | Direction::Right => {
                             // treat as empty - will move the light beam forward
                         }
-                        UP | DOWN => {
+                        Direction::Up | Direction::Down => {
                             // split the beam, this one continues left, and create a new one going right
-                            beam.direction = LEFT;
+                            beam.direction = Direction::Left;
                             let mut new_beam = Beam {
-                                direction: RIGHT,
+                                direction: Direction::Right,
                                 ..beam
                             };
                             new_beam.move_beam();
                             beams.push(new_beam);
                         }
-                        _ => panic!("invalid direction"),
                     }
                 }
                 MIRROR_BACK => {
                     // change direction of this beam based on the mirror \
                     beam.direction = match beam.direction {
-                        RIGHT => DOWN,
-                        DOWN => RIGHT,
-                        LEFT => UP,
-                        UP => LEFT,
-                        _ => panic!("invalid direction"),
+                        Direction::Right => Direction::Down,
+                        Direction::Down => Direction::Right,
+                        Direction::Left => Direction::Up,
+                        Direction::Up => Direction::Left,
                     }
                 }
                 MIRROR_FWD => {
                     // change direction of this beam based on the mirror /
                     beam.direction = match beam.direction {
-                        RIGHT => UP,
-                        DOWN => LEFT,
-                        LEFT => DOWN,
-                        UP => RIGHT,
-                        _ => panic!("invalid direction"),
+                        Direction::Right => Direction::Up,
+                        Direction::Down => Direction::Left,
+                        Direction::Left => Direction::Down,
+                        Direction::Up => Direction::Right,
                     }
                 }
                 _ => panic!(
@@ -145,7 +133,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let beam = Beam {
         x: 0,
         y: 0,
-        direction: RIGHT,
+        direction: Direction::Right,
     };
 
     Some(count_energised_tiles(&grid, beam))
@@ -164,7 +152,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         let beam = Beam {
             x: 0,
             y,
-            direction: RIGHT,
+            direction: Direction::Right,
         };
 
         max = max.max(count_energised_tiles(&grid, beam));
@@ -173,7 +161,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         let beam = Beam {
             x: w - 1,
             y,
-            direction: LEFT,
+            direction: Direction::Left,
         };
 
         max = max.max(count_energised_tiles(&grid, beam));
@@ -184,7 +172,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         let beam = Beam {
             x,
             y: 0,
-            direction: DOWN,
+            direction: Direction::Down,
         };
 
         max = max.max(count_energised_tiles(&grid, beam));
@@ -193,7 +181,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         let beam = Beam {
             x,
             y: h - 1,
-            direction: UP,
+            direction: Direction::Up,
         };
 
         max = max.max(count_energised_tiles(&grid, beam));
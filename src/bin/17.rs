@@ -1,34 +1,15 @@
+use advent_of_code::geometry::Direction;
+use advent_of_code::search::Search;
 use aoc_parse::{parser, prelude::*};
-use priority_queue::DoublePriorityQueue;
-use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(17);
 
-#[derive(Eq, Hash, PartialEq, Copy, Clone)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-    NONE,
-}
-impl Direction {
-    fn opposite(&self) -> Direction {
-        match *self {
-            Direction::UP => Direction::DOWN,
-            Direction::DOWN => Direction::UP,
-            Direction::LEFT => Direction::RIGHT,
-            Direction::RIGHT => Direction::LEFT,
-            Direction::NONE => Direction::NONE,
-        }
-    }
-}
-
 #[derive(Eq, Hash, PartialEq, Copy, Clone)]
 struct Position {
     x: i32,
     y: i32,
-    direction: Direction,
+    /// The direction this position was entered in, or `None` at the start.
+    direction: Option<Direction>,
 }
 impl Position {
     fn get_next(
@@ -36,7 +17,7 @@ impl Position {
         direction: Direction,
         min_span: i32,
         max_span: i32,
-        grid: &Vec<Vec<usize>>,
+        grid: &[Vec<usize>],
     ) -> Vec<(Position, usize)> {
         let mut result = vec![];
         let mut cost = 0;
@@ -44,28 +25,11 @@ impl Position {
         // Find all nodes in the specified direction that are within the span range and within
         // the grid boundaries, calculating the cumulative cost of reaching each of those nodes
         for i in 1..=max_span {
-            let new_pos = match direction {
-                Direction::UP => Position {
-                    x: self.x,
-                    y: self.y - i,
-                    direction,
-                },
-                Direction::DOWN => Position {
-                    x: self.x,
-                    y: self.y + i,
-                    direction,
-                },
-                Direction::LEFT => Position {
-                    x: self.x - i,
-                    y: self.y,
-                    direction,
-                },
-                Direction::RIGHT => Position {
-                    x: self.x + i,
-                    y: self.y,
-                    direction,
-                },
-                Direction::NONE => panic!("Invalid direction for next"),
+            let offset = direction.offset();
+            let new_pos = Position {
+                x: self.x + offset.x as i32 * i,
+                y: self.y + offset.y as i32 * i,
+                direction: Some(direction),
             };
 
             // At a boundary, stop looking
@@ -99,48 +63,34 @@ pub fn calculate_heat_loss(input: &str, min_span: i32, max_span: i32) -> Option<
     let start = Position {
         x: 0,
         y: 0,
-        direction: Direction::NONE,
+        direction: None,
     };
     let dest = (grid[0].len() as i32 - 1, grid.len() as i32 - 1);
 
-    // Use Dijkstra's algorithm with a priority queue
-    let mut queue = DoublePriorityQueue::new();
-    queue.push(start, 0);
-
-    let mut cost_tally = FxHashMap::default();
-    cost_tally.insert(start, 0);
-
-    while let Some((current, cost)) = queue.pop_min() {
-        if (current.x, current.y) == dest {
-            return Some(cost as u32);
-        }
-
-        for direction in [
-            Direction::UP,
-            Direction::LEFT,
-            Direction::DOWN,
-            Direction::RIGHT,
-        ] {
-            if direction == current.direction || direction.opposite() == current.direction {
-                // if it's the same direction skip, as we've already created nodes for all valid spaces forward
-                // if it's the opposite direction skip, as we can't go backwards
-                continue;
-            }
-
+    // Use Dijkstra's algorithm, with a node for each position and the direction it was entered from
+    let found = Search::new(start).dijkstra(
+        |current| {
+            [
+                Direction::Up,
+                Direction::Left,
+                Direction::Down,
+                Direction::Right,
+            ]
+            .into_iter()
+            // if it's the same direction skip, as we've already created nodes for all valid spaces forward
+            // if it's the opposite direction skip, as we can't go backwards
+            .filter(|direction| {
+                Some(*direction) != current.direction
+                    && Some(direction.opposite()) != current.direction
+            })
             // Create nodes for all the possible nodes between min_span and max_span and their cumulative costs
-            for (n, inc_cost) in current.get_next(direction, min_span, max_span, &grid) {
-                let cost = inc_cost + cost;
-
-                // If the cost is lower than previously found (or has not been found), replace it with this node
-                // and add it to the priority queue
-                if cost < *cost_tally.get(&n).unwrap_or(&usize::MAX) {
-                    cost_tally.insert(n, cost);
-                    queue.push(n, cost);
-                }
-            }
-        }
-    }
-    None
+            .flat_map(|direction| current.get_next(direction, min_span, max_span, &grid))
+            .collect::<Vec<_>>()
+        },
+        |current| (current.x, current.y) == dest,
+    )?;
+
+    Some(found.cost as u32)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
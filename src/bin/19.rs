@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+use advent_of_code::{
+    dot::{Dot, Style},
+    interval::{box_volume, Interval},
+};
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -10,7 +15,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, graph = workflow_graph);
 
 #[derive(Debug, Clone)]
 enum Operation {
@@ -124,22 +129,19 @@ fn execute_workflow(
 }
 
 fn count_accepted_combinations(
-    segments: Vec<(usize, usize)>,
+    segments: Vec<Interval>,
     name: String,
     workflow_map: &HashMap<String, Vec<WorkflowRule>>,
-) -> usize {
+) -> i64 {
     // If this workflow path is rejected, then none of the segments will be accepted - return 0
-    if name == "R" {
+    // If a segment is empty, there are no combinations left to check
+    if name == "R" || segments.iter().any(Interval::is_empty) {
         return 0;
     }
     // If this workflow path is accepted, count the total combinations for the given segments
     // Multiply possible x * m * a * s to get all combinations
     if name == "A" {
-        return segments
-            .iter()
-            .map(|s| s.1 - s.0 + 1)
-            .reduce(|acc, e| acc * e)
-            .unwrap();
+        return box_volume(&segments);
     }
 
     // Otherwise, find the named workflow and process the rules in order given the segments
@@ -151,9 +153,13 @@ fn count_accepted_combinations(
                 // Conditional rule. Split the segment for the given category into two
                 // The successful set goes to the workflow, and the unsuccessful set continues on
                 // to further rules (remaining)
+                let value = *value as i64;
                 let (wf_seg, rem_seg) = match *op {
-                    Operation::LT => ((remaining[*cat].0, *value - 1), (*value, remaining[*cat].1)),
-                    Operation::GT => ((*value + 1, remaining[*cat].1), (remaining[*cat].0, *value)),
+                    Operation::LT => remaining[*cat].split_at(value),
+                    Operation::GT => {
+                        let (below, above) = remaining[*cat].split_at(value + 1);
+                        (above, below)
+                    }
                 };
                 // Take the remaining segments and replace one segment to pass to the workflow
                 let mut wf = remaining.clone();
@@ -178,6 +184,32 @@ fn count_accepted_combinations(
     unreachable!()
 }
 
+/// The workflows, with an edge for each rule labelled by its condition, for `cargo solve 19 --graph`.
+fn workflow_graph(input: &str) -> Dot {
+    let (_, workflow_map) = parse(input);
+
+    let mut dot = Dot::digraph("workflows").with_style(Style::new().set("rankdir", "LR"));
+    dot.node("in", Style::new().shape("doublecircle"));
+    dot.node("A", Style::new().color("green").style("bold"));
+    dot.node("R", Style::new().color("red").style("bold"));
+    for (name, rules) in workflow_map.iter().sorted_by_key(|(name, _)| *name) {
+        for rule in rules {
+            match rule {
+                WorkflowRule::Rule(cat, op, value, workflow) => {
+                    let op = match op {
+                        Operation::LT => '<',
+                        Operation::GT => '>',
+                    };
+                    let condition = format!("{}{op}{value}", "xmas".as_bytes()[*cat] as char);
+                    dot.edge(name, workflow, Style::new().label(condition));
+                }
+                WorkflowRule::Command(cmd) => dot.edge(name, cmd, Style::new().style("dashed")),
+            }
+        }
+    }
+    dot
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     let (parts, workflow_map) = parse(input);
 
@@ -186,19 +218,19 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(
         parts
             .iter()
-            .filter(|part| execute_workflow(String::from("in"), &workflow_map, &part))
+            .filter(|part| execute_workflow(String::from("in"), &workflow_map, part))
             .map(|part| part.iter().sum::<usize>())
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Option<i64> {
     let (_, workflow_map) = parse(input);
 
     // Traverse the workflows counting accepted combinations across the segments
     // starting with 1 - 4000 for each
     Some(count_accepted_combinations(
-        vec![(1, 4000); 4],
+        vec![Interval::inclusive(1, 4000); 4],
         String::from("in"),
         &workflow_map,
     ))
@@ -1,6 +1,11 @@
 use core::panic;
 use std::collections::VecDeque;
 
+use advent_of_code::{
+    dot::{Dot, Style},
+    number_theory::first_coincidence,
+    vis::{Cell, Color, Frame, Recorder},
+};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, newline, one_of},
@@ -9,22 +14,21 @@ use nom::{
     sequence::{terminated, tuple},
     IResult,
 };
-use num::Integer;
 use rustc_hash::FxHashMap;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, graph = module_graph);
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 enum Pulse {
-    LOW,
-    HIGH,
+    Low,
+    High,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 enum ModuleType {
-    BROADCASTER,
-    FLIPFLOP,    // NOT gate
-    CONJUNCTION, // NAND gate
+    Broadcaster,
+    FlipFlop,    // NOT gate
+    Conjunction, // NAND gate
 }
 
 #[derive(Debug, Clone)]
@@ -47,11 +51,11 @@ fn parse_mapping(input: &str) -> IResult<&str, Module> {
         Module {
             name: name.to_string(),
             mod_type: match t {
-                Some('%') => ModuleType::FLIPFLOP,
-                Some('&') => ModuleType::CONJUNCTION,
+                Some('%') => ModuleType::FlipFlop,
+                Some('&') => ModuleType::Conjunction,
                 None => {
                     assert_eq!(name, "broadcaster");
-                    ModuleType::BROADCASTER
+                    ModuleType::Broadcaster
                 }
                 _ => panic!(),
             },
@@ -65,6 +69,23 @@ fn parse(input: &str) -> Vec<Module> {
     mappings
 }
 
+/// The modules and the cables between them, for `cargo solve 20 --graph`.
+fn module_graph(input: &str) -> Dot {
+    let mut dot = Dot::digraph("modules");
+    for module in parse(input) {
+        let (label, shape) = match module.mod_type {
+            ModuleType::Broadcaster => (module.name.clone(), "doublecircle"),
+            ModuleType::FlipFlop => (format!("%{}", module.name), "box"),
+            ModuleType::Conjunction => (format!("&{}", module.name), "diamond"),
+        };
+        dot.node(&module.name, Style::new().label(label).shape(shape));
+        for cable in &module.cables {
+            dot.edge(&module.name, cable, Style::new());
+        }
+    }
+    dot
+}
+
 struct Machine {
     modules: Vec<Module>,
     module_lookup: FxHashMap<String, usize>,
@@ -115,20 +136,20 @@ impl Machine {
         let mut queue = VecDeque::new();
 
         // push the button, send a low pulse to the broadcaster
-        queue.push_back((Pulse::LOW, usize::MAX, self.module_lookup["broadcaster"]));
+        queue.push_back((Pulse::Low, usize::MAX, self.module_lookup["broadcaster"]));
 
         // Process any pulses still remaining in this step
         while let Some((pulse, from_idx, idx)) = queue.pop_front() {
             let module = &self.modules[idx];
             if let Some(send) = match module.mod_type {
-                ModuleType::FLIPFLOP => {
-                    if pulse == Pulse::LOW {
+                ModuleType::FlipFlop => {
+                    if pulse == Pulse::Low {
                         // Toggle state
                         self.state[idx] = 1 - self.state[idx];
                         // Return the new state as a pulse to send on
                         Some(match self.state[idx] {
-                            0 => Pulse::LOW,
-                            1 => Pulse::HIGH,
+                            0 => Pulse::Low,
+                            1 => Pulse::High,
                             _ => unreachable!(),
                         })
                     } else {
@@ -136,10 +157,10 @@ impl Machine {
                         None
                     }
                 }
-                ModuleType::CONJUNCTION => {
+                ModuleType::Conjunction => {
                     // update memory for that input. First clear bit, then set if high.
                     self.state[idx] &= !(1 << from_idx);
-                    if pulse == Pulse::HIGH {
+                    if pulse == Pulse::High {
                         self.state[idx] |= 1 << from_idx;
                     }
 
@@ -149,14 +170,14 @@ impl Machine {
                             .iter()
                             .all(|x| self.state[idx] & (1 << x) != 0)
                         {
-                            Pulse::LOW
+                            Pulse::Low
                         } else {
-                            Pulse::HIGH
+                            Pulse::High
                         },
                     )
                 }
                 // Passthrough
-                ModuleType::BROADCASTER => Some(pulse),
+                ModuleType::Broadcaster => Some(pulse),
             } {
                 // Emit a pulses from this module to each of the cables
                 emit_pulses(module, send);
@@ -182,25 +203,34 @@ pub fn part_one(input: &str) -> Option<u32> {
     for _ in 0..1000 {
         low_total += 1; // add one for the button push
         machine.push_button(&mut |module, send| match send {
-            Pulse::LOW => low_total += module.cables.len() as u32,
-            Pulse::HIGH => high_total += module.cables.len() as u32,
+            Pulse::Low => low_total += module.cables.len() as u32,
+            Pulse::High => high_total += module.cables.len() as u32,
         });
     }
 
     Some(low_total * high_total)
 }
 
-fn _print_state(state: &Vec<u64>, modules: &Vec<Module>, value_keys: &mut FxHashMap<u64, u16>) {
-    for (i, v) in state.iter().enumerate() {
-        if modules[i].mod_type == ModuleType::CONJUNCTION {
-            let next = value_keys.len() as u16;
-            let c = value_keys.entry(*v).or_insert(next);
-            print!("{:3x} ", c);
-        } else {
-            print!("{:3} ", v);
-        }
-    }
-    println!();
+/// Draws the state of each module: whether a flip-flop is on, and a colour for each distinct
+/// combination of inputs remembered by a conjunction.
+fn state_cells(
+    state: &[u64],
+    modules: &[Module],
+    value_keys: &mut FxHashMap<u64, usize>,
+) -> Vec<Cell> {
+    state
+        .iter()
+        .zip(modules)
+        .map(|(&v, module)| match module.mod_type {
+            ModuleType::Conjunction => {
+                let next = value_keys.len();
+                let key = *value_keys.entry(v).or_insert(next);
+                Cell::new('&').bg(Color::indexed(key))
+            }
+            _ if v == 1 => Cell::new('1').bg(Color::WHITE),
+            _ => Cell::new('0'),
+        })
+        .collect()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -213,7 +243,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         .iter()
         .find(|m| m.cables.contains(&"rx".to_string()))
         .unwrap();
-    assert_eq!(start.mod_type, ModuleType::CONJUNCTION);
+    assert_eq!(start.mod_type, ModuleType::Conjunction);
 
     // Get the conjunctions that are in front of the above
     // Looking for cycle when all these send high at the same time
@@ -223,31 +253,58 @@ pub fn part_two(input: &str) -> Option<usize> {
         .filter_map(|m| m.cables.contains(&start.name).then_some(m.name.to_owned()))
         .collect::<Vec<_>>();
 
-    // Find the first cycle length for each of the listed gates
-    // Note we have an assumption that they have a consistent cycle from 0 based on the input
-    let mut gate_cycles = FxHashMap::default();
-    let mut gates_cycled = 0;
+    // Record the first two button presses on which each of the listed gates sends high, giving the
+    // offset and length of its cycle
+    let mut gate_highs: FxHashMap<String, Vec<i64>> = FxHashMap::default();
     let mut step = 1;
-    while gates_cycled < gates.len() {
+
+    // With --vis, draw the state of the modules after every press, one row per press
+    let mut recorder = Recorder::new(DAY, "part_two").map(|r| r.scale(2));
+    let (mut timeline, mut value_keys) = (vec![], FxHashMap::default());
+
+    while gates
+        .iter()
+        .any(|g| gate_highs.get(g).map_or(0, Vec::len) < 2)
+    {
         machine.push_button(&mut |module, send| {
-            if module.mod_type == ModuleType::CONJUNCTION && send == Pulse::HIGH {
-                if !gate_cycles.contains_key(&module.name) {
-                    gate_cycles.insert(module.name.clone(), step);
-                    if gates.contains(&module.name) {
-                        gates_cycled += 1;
-                    }
+            if send == Pulse::High && gates.contains(&module.name) {
+                let highs = gate_highs.entry(module.name.clone()).or_default();
+                if highs.len() < 2 && highs.last() != Some(&step) {
+                    highs.push(step);
                 }
             }
         });
         step += 1;
+
+        if recorder.is_some() {
+            timeline.extend(state_cells(
+                &machine.state,
+                &machine.modules,
+                &mut value_keys,
+            ));
+        }
+    }
+
+    if let Some(recorder) = &mut recorder {
+        let frame = Frame::from_cells(machine.modules.len(), timeline);
+        // The drawing is only a by-product, so report a failure without losing the answer
+        if let Err(e) = recorder.record(&frame) {
+            eprintln!("Error: could not write the frame of part two: {e}");
+        }
+    }
+
+    for (gate, highs) in &gate_highs {
+        advent_of_code::trace!(Debug, "{gate} first sends high on presses {highs:?}");
     }
 
-    // Calculate the LCM of all the cycle lengths to determine when all of those gates will be HIGH
-    // simultaneously, which will result in LOW to rx
-    gate_cycles
-        .values()
-        .map(|v| *v)
-        .reduce(|acc, e| acc.lcm(&e))
+    // Find the first press on which all of those gates will be HIGH simultaneously, which will result
+    // in LOW to rx. In the input each cycle starts from 0, making this the LCM of the cycle lengths.
+    first_coincidence(
+        gate_highs
+            .values()
+            .map(|highs| (highs[0], highs[1] - highs[0])),
+    )
+    .map(|press| press as usize)
 }
 
 #[cfg(test)]
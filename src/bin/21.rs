@@ -1,175 +1,169 @@
-use rustc_hash::FxHashSet;
+use std::collections::VecDeque;
+
+use advent_of_code::{
+    error::{Error, Result},
+    geometry::Point,
+    grid::Grid,
+    sequence::Polynomial,
+    vis::{Cell, Color, Frame, Recorder},
+};
+use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(21);
 
-fn count_destinations(input: &str, num_steps: u32) -> Option<usize> {
-    let lines = input.trim().split("\n").collect::<Vec<_>>();
-    let size = lines.len();
-    assert_eq!(lines[0].len(), size);
-
-    // Build initial grid from the input
-    let mut grid = vec![vec![0; size]; size];
-    let mut start = None;
-
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            match c {
-                '.' => (),
-                '#' => grid[y][x] = 1,
-                'S' => start = Some((y as i32, x as i32)),
-                _ => panic!("Invalid input"),
+/// Finds the fewest steps to reach every plot within `max_steps` of the start, in the infinitely
+/// repeating garden.
+fn step_counts(rocks: &Grid<bool>, start: Point, max_steps: u32) -> FxHashMap<Point, u32> {
+    let mut steps = FxHashMap::default();
+    steps.insert(start, 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((location, distance)) = queue.pop_front() {
+        if distance == max_steps {
+            continue;
+        }
+        for next in location.neighbours4() {
+            // The garden repeats, so wrap around to find whether there is a rock
+            if !rocks.get_wrapping(next.x as isize, next.y as isize) && !steps.contains_key(&next) {
+                steps.insert(next, distance + 1);
+                queue.push_back((next, distance + 1));
             }
         }
     }
+    steps
+}
 
-    // Build a list of locations that could be visited, beginning at the start point
-    let mut locations = FxHashSet::default();
-    locations.insert(start.unwrap());
-
-    // If we get to 7x7 gardens for part two, we know the pattern of the input that can be used
-    // to infer the answer
-    const GARDEN_SIZE: usize = 7;
-
-    // Iterate through all the required steps, but abort when we reach a conssistent pattern
-    for _ in 0..num_steps {
-        // Replace the current locations with a new set as we'll  change to the surrounding
-        let mut new_locations = FxHashSet::default();
-        let mut done = false;
-        for (x, y) in locations {
-            // We use rem_euclid to determine the relative position within the original grid since
-            // it is infinitely repeating outwards
-
-            // Check each direction and if no rock there, then add a new possible location
-            // If we've reached the edge of the 7x7 grid, mark as done
-
-            // Check left (x - 1, y)
-            if grid[y.rem_euclid(size as i32) as usize][(x - 1).rem_euclid(size as i32) as usize]
-                == 0
-            {
-                new_locations.insert((x - 1, y));
-                if x == -((GARDEN_SIZE / 2) as i32 * size as i32) + 1 {
-                    done = true;
-                }
-            }
-            // Check up (x, y - 1)
-            if grid[(y - 1).rem_euclid(size as i32) as usize][x.rem_euclid(size as i32) as usize]
-                == 0
-            {
-                new_locations.insert((x, y - 1));
-                if y == -((GARDEN_SIZE / 2) as i32 * size as i32) + 1 {
-                    done = true;
-                }
-            }
-            // Check right (x + 1, y)
-            if grid[y.rem_euclid(size as i32) as usize][(x + 1).rem_euclid(size as i32) as usize]
-                == 0
-            {
-                new_locations.insert((x + 1, y));
-                if x == size as i32 * (GARDEN_SIZE / 2 + 1) as i32 - 1 {
-                    done = true;
-                }
-            }
-            // Check down (x, y + 1)
-            if grid[(y + 1).rem_euclid(size as i32) as usize][x.rem_euclid(size as i32) as usize]
-                == 0
-            {
-                new_locations.insert((x, y + 1));
-                if y == size as i32 * (GARDEN_SIZE / 2 + 1) as i32 - 1 {
-                    done = true;
-                }
-            }
+/// A plot can be the final location after exactly `num_steps` if it can be reached in fewer
+/// steps with the same parity, as the remainder can be spent stepping back and forth.
+fn count_reachable(steps: &FxHashMap<Point, u32>, num_steps: u32) -> usize {
+    steps
+        .values()
+        .filter(|&&s| s <= num_steps && s % 2 == num_steps % 2)
+        .count()
+}
+
+/// Counts the plots that can be the final location after `num_steps`. Beyond a few gardens this
+/// relies on the number of plots growing quadratically, as it does for puzzle inputs and, once the
+/// reachable area has spread over a few gardens, for the example.
+fn count_destinations(input: &str, num_steps: u32) -> Result<usize> {
+    let garden = Grid::parse(input.trim(), |c| c);
+    let size = garden.width() as u32;
+    if garden.height() as u32 != size {
+        return Err(Error::new(format!(
+            "expected a square garden, found {size}x{}",
+            garden.height()
+        )));
+    }
+    let (x, y) = garden
+        .position(|&c| c == 'S')
+        .ok_or_else(|| Error::new("the garden has no start 'S'"))?;
+    let start = Point::new(x as i64, y as i64);
+    let rocks = garden.map(|&c| c == '#');
+
+    // Once the reachable area spreads past the first gardens, it grows as a diamond of repeated
+    // gardens. Sampled every full garden width, the number of plots follows a quadratic, so take
+    // enough samples to both fit it and confirm it. Rocks near the start can delay this, so sample
+    // further out until the last samples agree
+    const SAMPLES: u32 = 4;
+    const MAX_GARDENS: u32 = 63;
+    let remainder = num_steps % size;
+    let mut gardens = SAMPLES - 1;
+    loop {
+        let last_sample = remainder + gardens * size;
+
+        // Few enough steps to simply count them
+        if num_steps <= last_sample {
+            let steps = step_counts(&rocks, start, num_steps);
+            record_plots(&rocks, start, &steps, num_steps, 0..=num_steps);
+            return Ok(count_reachable(&steps, num_steps));
         }
-        // Use the new set of locations for the next iteration
-        locations = new_locations;
-
-        // Check if we found the 7x7 boundary and can repeat from there
-        if done {
-            // Count the number of locations in each of the repeated gardens
-            let mut garden_count = vec![vec![0; GARDEN_SIZE as usize]; GARDEN_SIZE as usize];
-            let offset = size as i32 * (GARDEN_SIZE / 2) as i32;
-            for y in 0..size * GARDEN_SIZE {
-                for x in 0..size * GARDEN_SIZE {
-                    if locations.contains(&((x as i32 - offset), (y as i32 - offset))) {
-                        garden_count[y / size][x / size] += 1;
-                    }
-                }
-            }
 
-            // n is the number of full gardens we need to traverse to get the eventual answer
-            // Pattern of 7x7 repeats, which looks like
-            // . . A B a . .
-            // . A D E d a .
-            // A D E e E d a
-            // F E e E e E f
-            // G H E e E h g
-            // . G H E h g .
-            // . . G b g . .
-            // E and e will alternate internally and expand as gardens are filled, with the same numbers
-            // on the boundaries at each full interval
-            // So for 7x7, n is 3
-            // The total number is the sum of the gardens. There will be:
-            //            n of A, a, G, g along the boundary
-            //      (n - 1) of D, d, H, h along the inside of the boundary that are not yet full
-            //       single of F, f, B, b at the points of the diamond
-            //        n ^ 2 of E the area of the inner diamond (odd distances)
-            //  (n - 1) ^ 2 of e the area of the inner diamond (even distances)
-
-            let n = num_steps as usize / size;
-            let total = n
-                * (garden_count[1][1] // A
-                    + garden_count[5][1] // a
-                    + garden_count[1][5] // G
-                    + garden_count[5][5]) // g
-                + (n - 1)
-                    * (garden_count[1][2] // D
-                        + garden_count[1][4] // d
-                        + garden_count[5][2] // H
-                        + garden_count[5][4]) // h
-                + garden_count[0][3] // F
-                + garden_count[6][3] // f
-                + garden_count[3][0] // B
-                + garden_count[3][6] // b
-                + n * n * garden_count[1][3] // E
-                + (n - 1) * (n - 1) * garden_count[2][3]; // e
-
-            // Abort early with this total
-            return Some(total);
+        let steps = step_counts(&rocks, start, last_sample);
+        record_plots(
+            &rocks,
+            start,
+            &steps,
+            num_steps,
+            (0..=gardens).map(|i| remainder + i * size),
+        );
+        let samples = (0..=gardens)
+            .map(|i| count_reachable(&steps, remainder + i * size) as i64)
+            .collect::<Vec<_>>();
+        advent_of_code::trace!(Debug, "plots reachable every {size} steps: {samples:?}");
+
+        let first = gardens + 1 - SAMPLES;
+        let polynomial = Polynomial::from_sequence(&samples[first as usize..]);
+        if polynomial.degree().is_some_and(|d| d <= 2) {
+            return polynomial
+                .eval_i64((num_steps / size - first) as i64)
+                .map(|total| total as usize)
+                .ok_or_else(|| Error::new("the number of plots does not fit in an i64"));
         }
-    }
 
-    // Return the total reached
-    Some(locations.len())
+        if gardens == MAX_GARDENS {
+            return Err(Error::new(format!(
+                "plots reachable every {size} steps do not grow quadratically: {samples:?}"
+            )));
+        }
+        gardens = (2 * gardens + 1).min(MAX_GARDENS);
+    }
 }
 
-fn _print_locations(locations: &FxHashSet<(i32, i32)>, grid: &Vec<Vec<i32>>) {
-    const GARDEN_SIZE: i32 = 3;
-
-    let (w, h) = (grid[0].len() as i32, grid.len() as i32);
-    for y in -h * (GARDEN_SIZE / 2)..h * (GARDEN_SIZE / 2 + 1) {
-        for x in -w * (GARDEN_SIZE / 2)..w * (GARDEN_SIZE / 2 + 1) {
-            if locations.contains(&(x as i32, y as i32)) {
-                print!("O");
+/// Draws the plots that can be the final location after `num_steps`, over the gardens within
+/// `radius` steps of the start.
+fn render_plots(
+    rocks: &Grid<bool>,
+    start: Point,
+    steps: &FxHashMap<Point, u32>,
+    num_steps: u32,
+    radius: i64,
+) -> Frame {
+    let cells = (-radius..=radius)
+        .flat_map(|y| (-radius..=radius).map(move |x| Point::new(start.x + x, start.y + y)))
+        .map(|p| {
+            if steps
+                .get(&p)
+                .is_some_and(|&s| s <= num_steps && s % 2 == num_steps % 2)
+            {
+                Cell::new('O').fg(Color::GREEN)
+            } else if *rocks.get_wrapping(p.x as isize, p.y as isize) {
+                Cell::new('#').fg(Color::GREY)
             } else {
-                print!(
-                    "{}",
-                    match grid[y.rem_euclid(h) as usize][x.rem_euclid(w) as usize] {
-                        0 => ".",
-                        1 => "#",
-                        _ => panic!(),
-                    }
-                );
+                Cell::new('.')
             }
+        })
+        .collect();
+    Frame::from_cells(2 * radius as usize + 1, cells)
+}
+
+/// With `--vis`, writes a frame of the reachable plots after each of the given numbers of steps.
+fn record_plots(
+    rocks: &Grid<bool>,
+    start: Point,
+    steps: &FxHashMap<Point, u32>,
+    num_steps: u32,
+    frames: impl Iterator<Item = u32> + Clone,
+) {
+    let Some(mut recorder) = Recorder::new(DAY, &format!("steps_{num_steps}")) else {
+        return;
+    };
+    let radius = frames.clone().max().unwrap_or(0) as i64;
+    for n in frames {
+        let frame = render_plots(rocks, start, steps, n, radius);
+        // The drawing is only a by-product, so report a failure and stop recording without
+        // losing the answer
+        if let Err(e) = recorder.record(&frame) {
+            eprintln!("Error: could not write the frame after {n} steps: {e}");
+            return;
         }
-        println!();
     }
-    println!();
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str) -> Result<usize> {
     count_destinations(input, 64)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Result<usize> {
     count_destinations(input, 26501365)
 }
 
@@ -180,28 +174,18 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = count_destinations(&advent_of_code::template::read_file("examples", DAY), 6);
-        assert_eq!(result, Some(16));
+        assert_eq!(result, Ok(16));
     }
 
     #[test]
     fn test_part_two() {
-        let result = count_destinations(&advent_of_code::template::read_file("examples", DAY), 6);
-        assert_eq!(result, Some(16));
-        let result = count_destinations(&advent_of_code::template::read_file("examples", DAY), 10);
-        assert_eq!(result, Some(50));
-
-        // TODO: the current approach works for the input data, but not for the examples because the number of steps do not exactly match the interval
-        // let result = count_destinations(&advent_of_code::template::read_file("examples", DAY), 50);
-        // assert_eq!(result, Some(1594));
-        // let result = count_destinations(&advent_of_code::template::read_file("examples", DAY), 100);
-        // assert_eq!(result, Some(6536));
-        // let result = count_destinations(&advent_of_code::template::read_file("examples", DAY), 500);
-        // assert_eq!(result, Some(167004));
-        // let result =
-        //     count_destinations(&advent_of_code::template::read_file("examples", DAY), 1000);
-        // assert_eq!(result, Some(668697));
-        // let result =
-        //     count_destinations(&advent_of_code::template::read_file("examples", DAY), 5000);
-        // assert_eq!(result, Some(16733044));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(count_destinations(&input, 6), Ok(16));
+        assert_eq!(count_destinations(&input, 10), Ok(50));
+        assert_eq!(count_destinations(&input, 50), Ok(1594));
+        assert_eq!(count_destinations(&input, 100), Ok(6536));
+        assert_eq!(count_destinations(&input, 500), Ok(167004));
+        assert_eq!(count_destinations(&input, 1000), Ok(668697));
+        assert_eq!(count_destinations(&input, 5000), Ok(16733044));
     }
 }
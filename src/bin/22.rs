@@ -1,74 +1,49 @@
 use std::collections::VecDeque;
 
+use advent_of_code::geometry::Point3;
 use itertools::Itertools;
 
 advent_of_code::solution!(22);
 
-#[derive(Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
 // Dimensions of the x, y grid
-const SIZE: i32 = 10;
+const SIZE: i64 = 10;
 
 // Possible height of bricks in the grid
-const Z_SIZE: i32 = 1000;
-
-impl Point {
-    fn from_str(s: &str) -> Self {
-        let (x, y, z) = s
-            .split(",")
-            .map(|v| i32::from_str_radix(v, 10).unwrap())
-            .collect_tuple()
-            .unwrap();
-        assert!(x >= 0 && y >= 0 && z > 0 && x < SIZE && y < SIZE && z < Z_SIZE);
-        Self { x, y, z }
-    }
-
-    fn diff(&self, p: &Point) -> (i32, i32, i32) {
-        (self.x - p.x, self.y - p.y, self.z - p.z)
-    }
-
-    fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
+const Z_SIZE: i64 = 1000;
+
+fn parse_point(s: &str) -> Point3 {
+    let (x, y, z) = s
+        .split(",")
+        .map(|v| v.parse::<i64>().unwrap())
+        .collect_tuple()
+        .unwrap();
+    assert!(x >= 0 && y >= 0 && z > 0 && x < SIZE && y < SIZE && z < Z_SIZE);
+    Point3::new(x, y, z)
 }
 
 #[derive(Debug)]
 struct Brick {
     id: usize,
-    cubes: Vec<Point>,
+    cubes: Vec<Point3>,
 }
 
 impl Brick {
-    fn new(id: usize, start: Point, end: Point) -> Self {
+    fn new(id: usize, start: Point3, end: Point3) -> Self {
         Self {
             id,
-            cubes: Brick::create_cubes(&start, &end),
+            cubes: Brick::create_cubes(start, end),
         }
     }
 
-    fn create_cubes(start: &Point, end: &Point) -> Vec<Point> {
+    fn create_cubes(start: Point3, end: Point3) -> Vec<Point3> {
         // Create a Point for each cube to easily compare against the grid
         // Since each is a single straight line, this generalised solution will
         // walk along the direction of whichever is nonzero
-        let (dx, dy, dz) = end.diff(start);
-        let brick_len = (dx + dy + dz).abs() + 1;
-        let (dir_x, dir_y, dir_z) = (dx.signum(), dy.signum(), dz.signum());
+        let diff = end - start;
+        let brick_len = (diff.x + diff.y + diff.z).abs() + 1;
+        let direction = Point3::new(diff.x.signum(), diff.y.signum(), diff.z.signum());
 
-        (0..brick_len)
-            .into_iter()
-            .map(|i| {
-                Point::new(
-                    start.x + i * dir_x,
-                    start.y + i * dir_y,
-                    start.z + i * dir_z,
-                )
-            })
-            .collect()
+        (0..brick_len).map(|i| start + direction * i).collect()
     }
 
     fn move_down(&mut self) {
@@ -171,11 +146,7 @@ fn parse_input(input: &str) -> Vec<Brick> {
         .split("\n")
         .enumerate()
         .map(|(i, line)| {
-            let (start, end) = line
-                .split("~")
-                .map(|p| Point::from_str(p))
-                .collect_tuple()
-                .unwrap();
+            let (start, end) = line.split("~").map(parse_point).collect_tuple().unwrap();
             Brick::new(i, start, end)
         })
         .collect_vec()
@@ -190,7 +161,7 @@ pub fn part_one(input: &str) -> Option<usize> {
         structure.len()
             - structure
                 .iter()
-                .filter_map(|supported_by| (supported_by.len() == 1).then_some(supported_by))
+                .filter(|supported_by| supported_by.len() == 1)
                 .unique()
                 .count(),
     )
@@ -202,7 +173,8 @@ pub fn part_two(input: &str) -> Option<usize> {
     // Use the part 1 solution to find all the bricks that will cause others to fall if disintegrated
     let to_disintegrate = structure
         .iter()
-        .filter_map(|supported_by| (supported_by.len() == 1).then(|| supported_by[0]))
+        .filter(|&supported_by| supported_by.len() == 1)
+        .map(|supported_by| supported_by[0])
         .unique()
         .collect::<Vec<_>>();
 
@@ -1,15 +1,21 @@
+use advent_of_code::{
+    error::{Error, Result},
+    geometry::Point3,
+    space::{Line, Plane, Vector},
+};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    character::complete::{i128, newline, space0},
+    character::complete::{i64, newline, space0},
     multi::{many1, separated_list1},
     sequence::{preceded, terminated, tuple},
     IResult,
 };
+use num::{BigInt, BigRational, Zero};
 
 advent_of_code::solution!(24);
 
-fn count_intersections(input: &str, min: i128, max: i128) -> Option<usize> {
+fn count_intersections(input: &str, min: i64, max: i64) -> Option<usize> {
     let hailstones = parse_input(input);
 
     Some(
@@ -24,9 +30,9 @@ fn count_intersections(input: &str, min: i128, max: i128) -> Option<usize> {
 fn parse_input(input: &str) -> Vec<Hailstone> {
     fn parse_trajectory(input: &str) -> IResult<&str, Hailstone> {
         let (input, (pos, _, velocity)) = tuple((
-            separated_list1(tag(", "), i128),
+            separated_list1(tag(", "), i64),
             tag(" @ "),
-            separated_list1(tag(", "), preceded(space0, i128)),
+            separated_list1(tag(", "), preceded(space0, i64)),
         ))(input)?;
 
         Ok((input, Hailstone::from(pos, velocity)))
@@ -36,143 +42,72 @@ fn parse_input(input: &str) -> Vec<Hailstone> {
     hailstones
 }
 
-#[derive(Debug)]
-struct Coordinate {
-    x: i128,
-    y: i128,
-    z: i128,
+struct Hailstone {
+    pos: Point3,
+    velocity: Point3,
 }
 
-impl Coordinate {
-    fn new(x: i128, y: i128, z: i128) -> Self {
-        Self { x, y, z }
-    }
-
-    fn diff(&self, other: &Self) -> Self {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
-        }
-    }
-
-    fn add(&self, other: &Self) -> Self {
+impl Hailstone {
+    fn from(pos: Vec<i64>, velocity: Vec<i64>) -> Self {
         Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
+            pos: Point3::new(pos[0], pos[1], pos[2]),
+            velocity: Point3::new(velocity[0], velocity[1], velocity[2]),
         }
     }
 
-    fn cross_product(&self, other: &Coordinate) -> Coordinate {
-        // cross product to get normal vector
-        Coordinate::new(
-            self.y * other.z - self.z * other.y,
-            self.z * other.x - self.x * other.z,
-            self.x * other.y - self.y * other.x,
-        )
-    }
-
-    fn div(&self, dt: i128) -> Self {
-        Self {
-            x: self.x / dt,
-            y: self.y / dt,
-            z: self.z / dt,
-        }
+    /// The path of the hailstone, where the parameter of each point is the time it is reached.
+    fn line(&self) -> Line {
+        Line::new(self.pos.into(), self.velocity.into())
     }
-}
-
-type Point = Coordinate;
-type Velocity = Coordinate;
 
-struct Hailstone {
-    pos: Point,
-    velocity: Velocity,
-}
-
-impl Hailstone {
-    fn new(pos: Point, velocity: Velocity) -> Self {
-        Self { pos, velocity }
+    /// The path of the hailstone when ignoring the Z axis.
+    fn line_xy(&self) -> Line {
+        let flatten = |p: Point3| Vector::from_ints(p.x, p.y, 0);
+        Line::new(flatten(self.pos), flatten(self.velocity))
     }
 
-    fn from(pos: Vec<i128>, velocity: Vec<i128>) -> Self {
-        Self {
-            pos: Point {
-                x: pos[0],
-                y: pos[1],
-                z: pos[2],
-            },
-            velocity: Velocity {
-                x: velocity[0],
-                y: velocity[1],
-                z: velocity[2],
-            },
-        }
-    }
-
-    fn intersection_with_plane(&self, cp: &Coordinate) -> (i128, Point) {
-        // Solve for t by substituting this into the equation of the plane:
-        // hailstone line equation
-        //   (p.x + v.x * t, p.y + v.y * t, p.z + v.z * t)
-        // substituded into plane and collect like terms:
-        //   cp_x * p.x + cp_y * p.y + cp_z * p.z + t * (cp_x * v.x + cp_y * v.y + cp_z * v.z)
-        // solve for t:
-        //   t = -(cp_x * p.x + cp_y * p.y + cp_z * p.z) / (cp_x * v.x + cp_y * v.y + cp_z * v.z)
-        let (p, v) = (&self.pos, &self.velocity);
-        let t = -(cp.x * p.x + cp.y * p.y + cp.z * p.z) / (cp.x * v.x + cp.y * v.y + cp.z * v.z);
-        let intersection = Point::new(p.x + v.x * t, p.y + v.y * t, p.z + v.z * t);
-        (t, intersection)
+    /// The hailstone as seen by an observer travelling with `other`.
+    fn relative_to(&self, other: &Hailstone) -> Option<Hailstone> {
+        Some(Hailstone {
+            pos: self.pos.checked_sub(other.pos)?,
+            velocity: self.velocity.checked_sub(other.velocity)?,
+        })
     }
 }
 
-fn check_intersection(
-    hailstone1: &Hailstone,
-    hailstone2: &Hailstone,
-    min: i128,
-    max: i128,
-) -> bool {
-    // velocity is the vector form of the line
-    // we can represent both lines as an equation and solve them simultaneously
-    // to find the x, y that fits both
-    let (pos1, vel1) = (&hailstone1.pos, &hailstone1.velocity);
-    let c1 = vel1.y * pos1.x - vel1.x * pos1.y;
-
-    let (pos2, vel2) = (&hailstone2.pos, &hailstone2.velocity);
-    let c2 = vel2.y * pos2.x - vel2.x * pos2.y;
-
-    let d = vel2.y * vel1.x - vel1.y * vel2.x;
-    if d == 0 {
-        // parallel lines
+fn check_intersection(hailstone1: &Hailstone, hailstone2: &Hailstone, min: i64, max: i64) -> bool {
+    // Solve the two paths simultaneously, giving the time each hailstone reaches the crossing
+    // point. Parallel paths never cross.
+    let (line1, line2) = (hailstone1.line_xy(), hailstone2.line_xy());
+    let Some((t1, t2)) = line1.intersect(&line2) else {
+        return false;
+    };
+    // Ignore crossings that happened in the past for either hailstone
+    if t1 <= BigRational::zero() || t2 <= BigRational::zero() {
         return false;
     }
 
-    // find the intersection point
-    let int_x = (vel1.x * c2 - vel2.x * c1) / d;
-    let int_y = (vel1.y * c2 - vel2.y * c1) / d;
-    // check signum to determine which side of the starting position the intersection
-    // needs to be on to avoid intersections that happen in the past
-    // TODO: can we solve for t on a parametric equation instead?
-    if (vel1.x.signum() > 0 || int_x < pos1.x)
-        && (vel1.x.signum() < 0 || int_x > pos1.x)
-        && (vel1.y.signum() > 0 || int_y < pos1.y)
-        && (vel1.y.signum() < 0 || int_y > pos1.y)
-        && (vel2.x.signum() > 0 || int_x < pos2.x)
-        && (vel2.x.signum() < 0 || int_x > pos2.x)
-        && (vel2.y.signum() > 0 || int_y < pos2.y)
-        && (vel2.y.signum() < 0 || int_y > pos2.y)
-    {
-        // determine if the intersection point is within the boundary defined
-        return int_x >= min && int_x <= max && int_y >= min && int_y <= max;
-    }
-    return false;
+    // determine if the intersection point is within the boundary defined
+    let crossing = line1.at(&t1);
+    let (min, max) = (
+        BigRational::from_integer(BigInt::from(min)),
+        BigRational::from_integer(BigInt::from(max)),
+    );
+    crossing.x >= min && crossing.x <= max && crossing.y >= min && crossing.y <= max
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     count_intersections(input, 200_000_000_000_000, 400_000_000_000_000)
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
+pub fn part_two(input: &str) -> Result<i64> {
     let hailstones = parse_input(input);
+    if hailstones.len() < 4 {
+        return Err(Error::new(format!(
+            "expected at least 4 hailstones, found {}",
+            hailstones.len()
+        )));
+    }
 
     // To find the path of the rock, we move all the hailstones into the frame of
     // reference of the first hailstone. This means it will be stationary at the origin
@@ -188,51 +123,53 @@ pub fn part_two(input: &str) -> Option<i128> {
 
     // Translate hailstones into the frame of reference of the first
     let first_hailstone = &hailstones[0];
-    let translated_hailstones = hailstones[1..]
+    let translated_hailstones = hailstones[1..4]
         .iter()
-        .map(|h| {
-            Hailstone::new(
-                h.pos.diff(&first_hailstone.pos),
-                h.velocity.diff(&first_hailstone.velocity),
-            )
-        })
-        .collect::<Vec<_>>();
+        .map(|h| h.relative_to(first_hailstone).map(|h| h.line()))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::new("hailstone coordinates overflow"))?;
 
-    // Find the plane between the origin and the line of the first hailstone
-    // First vector = velocity of the next hailstone
-    // Second vector = origin and any point on the line of that hailstone (we use the starting point)
-    // Taking the cross product gives the normal vector, which can be used to represent the plane
-    // cp = (A, B, C) then plane is Ax + By + Cz = 0 (no additional constant due to being through the origin)
+    // The plane through the origin containing the line of the next hailstone, spanned by its
+    // velocity and its starting point
     let next_hailstone = &translated_hailstones[0];
-    let cp = next_hailstone.velocity.cross_product(&next_hailstone.pos);
+    let plane = Plane::through(
+        &Vector::zero(),
+        &next_hailstone.direction,
+        &next_hailstone.point,
+    )
+    .ok_or_else(|| Error::new("the first two hailstones do not define a plane"))?;
 
     // Find intersection between two other hailstones and the plane, used to
     // determine the trajectory of the rock
-    let (t1, intersection1) = translated_hailstones[1].intersection_with_plane(&cp);
-    let (t2, intersection2) = translated_hailstones[2].intersection_with_plane(&cp);
+    let parallel = || Error::new("a hailstone travels parallel to the plane of the rock");
+    let t1 = translated_hailstones[1]
+        .intersect_plane(&plane)
+        .ok_or_else(parallel)?;
+    let t2 = translated_hailstones[2]
+        .intersect_plane(&plane)
+        .ok_or_else(parallel)?;
+    let (intersection1, intersection2) = (
+        translated_hailstones[1].at(&t1),
+        translated_hailstones[2].at(&t2),
+    );
 
     // Find the amount of time between the two intersections and then normalise the vector accordingly
-    let dt = t2 - t1;
-    let v = intersection2.diff(&intersection1).div(dt);
-
-    // Rock line will be:
-    //   x = start.x + v.x * t
-    //   y = start.y + v.y * t
-    //   z = start.z + v.z * t
-    // therefore to find the starting position:
-    //   start.x = x - v.x * t
-    //   start.y = y - v.y * t
-    //   start.z = z - v.z * t
-    let start = Point::new(
-        intersection1.x - v.x * t1,
-        intersection1.y - v.y * t1,
-        intersection1.z - v.z * t1,
-    );
+    let dt = &t2 - &t1;
+    if dt.is_zero() {
+        return Err(Error::new("two hailstones hit the rock at the same time"));
+    }
+    let velocity = (&intersection2 - &intersection1).scale(&dt.recip());
 
-    // Put back into original frame of reference
-    let start = start.add(&first_hailstone.pos);
+    // Walking back along the rock's path from the first collision gives its starting position
+    let rock = Line::new(intersection1, velocity);
+    let start = rock.at(&-t1);
 
-    Some(start.x + start.y + start.z)
+    // Put back into original frame of reference. The throw must start from integer coordinates.
+    let start = (&start + &first_hailstone.pos.into())
+        .to_point3()
+        .ok_or_else(|| Error::new("the rock does not start from integer coordinates"))?;
+
+    Ok(start.x + start.y + start.z)
 }
 
 #[cfg(test)]
@@ -260,84 +197,68 @@ mod tests {
         ));
 
         // Hailstones' paths will cross outside the test area (at x=6.2, y=19.4).
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![19, 13, 30], vec![-2, 1, -2]),
-                &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![19, 13, 30], vec![-2, 1, -2]),
+            &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
+            7,
+            27
+        ));
 
         // Hailstones' paths crossed in the past for hailstone A.
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![19, 13, 30], vec![-2, 1, -2]),
-                &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![19, 13, 30], vec![-2, 1, -2]),
+            &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
+            7,
+            27
+        ));
 
         // Hailstones' paths are parallel; they never intersect.
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![18, 19, 22], vec![-1, -1, -2]),
-                &Hailstone::from(vec![20, 25, 34], vec![-2, -2, -4]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![18, 19, 22], vec![-1, -1, -2]),
+            &Hailstone::from(vec![20, 25, 34], vec![-2, -2, -4]),
+            7,
+            27
+        ));
 
         // Hailstones' paths will cross outside the test area (at x=-6, y=-5).
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![18, 19, 22], vec![-1, -1, -2]),
-                &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![18, 19, 22], vec![-1, -1, -2]),
+            &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
+            7,
+            27
+        ));
 
         // Hailstones' paths crossed in the past for both hailstones.
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![18, 19, 22], vec![-1, -1, -2]),
-                &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![18, 19, 22], vec![-1, -1, -2]),
+            &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
+            7,
+            27
+        ));
 
         // Hailstones' paths will cross outside the test area (at x=-2, y=3).
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![20, 25, 34], vec![-2, -2, -4]),
-                &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![20, 25, 34], vec![-2, -2, -4]),
+            &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
+            7,
+            27
+        ));
 
         // Hailstones' paths crossed in the past for hailstone B.
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![20, 25, 34], vec![-2, -2, -4]),
-                &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![20, 25, 34], vec![-2, -2, -4]),
+            &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
+            7,
+            27
+        ));
 
         // Hailstones' paths crossed in the past for both hailstones.
-        assert!(
-            check_intersection(
-                &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
-                &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
-                7,
-                27
-            ) == false
-        );
+        assert!(!check_intersection(
+            &Hailstone::from(vec![12, 31, 28], vec![-1, -2, -1]),
+            &Hailstone::from(vec![20, 19, 15], vec![1, -5, -3]),
+            7,
+            27
+        ));
     }
 
     #[test]
@@ -350,6 +271,21 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(47));
+        assert_eq!(result, Ok(47));
+    }
+
+    #[test]
+    fn test_part_two_errors() {
+        let error = part_two("19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2\n").unwrap_err();
+        assert_eq!(error.message, "expected at least 4 hailstones, found 2");
+
+        // The last two hailstones cross the plane z = 0 of the second at the same time
+        let input =
+            "0, 0, 0 @ 0, 0, 0\n1, 0, 0 @ 0, 1, 0\n0, 0, 1 @ 1, 0, -1\n5, 5, 1 @ 0, 0, -1\n";
+        let error = part_two(input).unwrap_err();
+        assert_eq!(
+            error.message,
+            "two hailstones hit the rock at the same time"
+        );
     }
 }
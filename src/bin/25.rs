@@ -1,54 +1,123 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, newline, space1},
-    combinator::opt,
-    multi::{many1, separated_list1},
-    sequence::{terminated, tuple},
-    IResult,
+use std::env;
+
+use advent_of_code::{
+    dot::{Dot, Style},
+    error::{Error, Result},
+    graph::{Graph, MinCut, NodeId},
+    parse::{lines, mapping},
 };
+use itertools::Itertools;
 use rand::seq::{IteratorRandom, SliceRandom};
+use rand::Rng;
 use rustc_hash::FxHashMap;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, graph = component_graph);
 
-fn string_to_id(s: &str) -> u32 {
-    let b = s.as_bytes();
-    (b[0] - b'a') as u32 * 26 * 26 + (b[1] - b'a') as u32 * 26 + (b[2] - b'a') as u32
-}
+fn parse(input: &str) -> Result<Graph> {
+    let connections = lines(input, |line| mapping(line, ":"))?;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, connections) = parse_input(input).unwrap();
-
-    // Graph represented as an adjacency list
-    let mut graph = FxHashMap::default();
-    for (from, _, to) in connections {
-        let from_id = string_to_id(from);
-        let mut to_ids = to.iter().map(|t| string_to_id(t)).collect::<Vec<u32>>();
-        for &to_id in &to_ids {
-            graph
-                .entry(to_id)
-                .and_modify(|v: &mut Vec<u32>| v.push(from_id))
-                .or_insert(vec![from_id]);
+    let mut components = Graph::new();
+    for (from, to) in connections {
+        for t in to {
+            components.add_undirected_edge(from, t);
         }
-        graph
-            .entry(from_id)
-            .and_modify(|v| v.append(&mut to_ids))
-            .or_insert(to_ids);
     }
+    Ok(components)
+}
+
+/// The wiring between components, coloured by the side of the minimum cut that each is on and
+/// with the cut wires in bold, for `cargo solve 25 --graph`.
+fn component_graph(input: &str) -> Result<Dot> {
+    let components = parse(input)?;
+    let cut = components.min_cut();
 
-    // Determine min cut with Karger's algorithm
-    // Alternative: Stoer–Wagner min cut algorithm - but this is not necessarily more efficient in unweighted graph
-    let mut rng = rand::thread_rng();
+    let side = |id| {
+        cut.as_ref()
+            .and_then(|cut| cut.partitions.iter().position(|p| p.contains(&id)))
+    };
+    Ok(Dot::from_graph(
+        "components",
+        &components,
+        false,
+        |id| match side(id) {
+            Some(0) => Style::new().color("blue"),
+            Some(_) => Style::new().color("darkgreen"),
+            None => Style::new(),
+        },
+        |a, b| match cut.as_ref().is_some_and(|cut| cut.edges.contains(&(a, b))) {
+            true => Style::new().color("red").style("bold"),
+            false => Style::new(),
+        },
+    )
+    .with_style(Style::new().set("layout", "sfdp")))
+}
+
+/// The number of wires the puzzle says must be disconnected.
+const WIRES: usize = 3;
+
+/// How many contractions Karger's algorithm tries before settling for the smallest cut found.
+const KARGER_ATTEMPTS: usize = 1000;
+
+/// The algorithm that finds the minimum cut. Stoer–Wagner is used unless the `DAY25_MIN_CUT`
+/// environment variable is `karger`, to compare the two with
+/// `DAY25_MIN_CUT=karger cargo time 25`.
+fn min_cut(components: &Graph) -> Option<MinCut> {
+    match env::var("DAY25_MIN_CUT").as_deref() {
+        Ok("karger") => karger_min_cut(components, &mut advent_of_code::random::rng()),
+        _ => components.min_cut(),
+    }
+}
+
+/// Finds the wires to disconnect, checking that there are as many as the puzzle says.
+fn split(components: &Graph) -> Result<MinCut> {
+    let cut = min_cut(components)
+        .ok_or_else(|| Error::new("the wiring needs at least two components to split"))?;
+
+    let wires = cut
+        .edges
+        .iter()
+        .map(|&(a, b)| format!("{}/{}", components.name(a), components.name(b)))
+        .join(", ");
+    let sizes = cut.partitions.each_ref().map(Vec::len);
+    advent_of_code::trace!(Debug, "cut {wires} into groups of {sizes:?}");
+
+    if cut.edges.len() != WIRES {
+        return Err(Error::new(format!(
+            "expected to disconnect {WIRES} wires, but the minimum cut has {}: {wires}",
+            cut.edges.len()
+        )));
+    }
+    Ok(cut)
+}
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let cut = split(&parse(input)?)?;
+    Ok(cut.partitions[0].len() * cut.partitions[1].len())
+}
+
+/// Finds a small cut with Karger's algorithm, contracting random edges until two groups of
+/// components remain. It stops at the first cut of [`WIRES`] wires, or returns the smallest cut
+/// of [`KARGER_ATTEMPTS`] tries, which is only likely to be the minimum.
+fn karger_min_cut(components: &Graph, rng: &mut impl Rng) -> Option<MinCut> {
+    if components.len() < 2 {
+        return None;
+    }
+    let graph: FxHashMap<NodeId, Vec<NodeId>> = (0..components.len())
+        .map(|id| (id, components.neighbours(id).to_vec()))
+        .collect();
 
-    loop {
+    let mut best: Option<MinCut> = None;
+    for attempt in 1..=KARGER_ATTEMPTS {
         let mut merged_vertices = FxHashMap::default();
 
         let mut contracted_graph = graph.clone();
         while contracted_graph.len() > 2 {
-            // pick a random edge (u, v)
-            let &u = contracted_graph.keys().choose(&mut rng).unwrap();
-            let &v = contracted_graph.get(&u).unwrap().choose(&mut rng).unwrap();
+            // pick a random edge (u, v), or any vertex if the graph is disconnected
+            let &u = contracted_graph.keys().choose(rng).unwrap();
+            let v = match contracted_graph.get(&u).unwrap().choose(rng) {
+                Some(&v) => v,
+                None => *contracted_graph.keys().find(|&&k| k != u).unwrap(),
+            };
 
             // contract u & v into a single vertex
             let mut v_edges = contracted_graph.get(&v).unwrap().clone();
@@ -72,7 +141,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
             merged_vertices
                 .entry(u)
-                .and_modify(|adj: &mut Vec<u32>| adj.push(v))
+                .and_modify(|adj: &mut Vec<NodeId>| adj.push(v))
                 .or_insert(vec![v]);
             if let Some(mut edges) = merged_vertices.remove(&v) {
                 merged_vertices
@@ -80,26 +149,55 @@ pub fn part_one(input: &str) -> Option<u32> {
                     .and_modify(|adj| adj.append(&mut edges));
             }
 
-            // _check_graph_integrity(&contracted_graph, v);
+            if advent_of_code::trace_enabled!(Trace) {
+                check_graph_integrity(&contracted_graph, v);
+            }
+        }
+
+        // When two vertices remain, their merged vertices are the two sides of the cut
+        let mut in_first = vec![false; components.len()];
+        let first = contracted_graph.keys().min().unwrap();
+        in_first[*first] = true;
+        for &id in merged_vertices.get(first).into_iter().flatten() {
+            in_first[id] = true;
         }
+        let cut = cut_between(components, &in_first);
+        advent_of_code::trace!(
+            Debug,
+            "attempt {attempt} found a cut of {} edges",
+            cut.edges.len()
+        );
 
-        // When two vertices remain, edges between them is the number of cuts
-        let (a, b) = contracted_graph.values().collect_tuple().unwrap();
-        let cut = a.len();
-        assert_eq!(cut, b.len());
-
-        // As we know from the problem that the minimum is 3, we stop at this repetition
-        if a.len() == 3 {
-            // get the count of vertices merged into each set, then multiply together
-            return contracted_graph
-                .keys()
-                .map(|k| merged_vertices.get(k).unwrap_or(&vec![]).len() as u32 + 1)
-                .reduce(|acc, e| acc * e);
+        let found = cut.edges.len() == WIRES;
+        if best
+            .as_ref()
+            .is_none_or(|b| cut.edges.len() < b.edges.len())
+        {
+            best = Some(cut);
         }
+        if found {
+            break;
+        }
+    }
+    best
+}
+
+/// The cut separating the components marked `true` from the rest.
+fn cut_between(components: &Graph, in_first: &[bool]) -> MinCut {
+    let edges = components
+        .edges()
+        .iter()
+        .copied()
+        .filter(|&(a, b)| in_first[a] != in_first[b])
+        .collect();
+    let (first, second) = (0..components.len()).partition(|&id| in_first[id]);
+    MinCut {
+        edges,
+        partitions: [first, second],
     }
 }
 
-fn _check_graph_integrity(contracted_graph: &FxHashMap<u32, Vec<u32>>, v: u32) {
+fn check_graph_integrity(contracted_graph: &FxHashMap<NodeId, Vec<NodeId>>, v: NodeId) {
     // Check that the adjacecy lists are being maintained correctly
     for (&key, value) in contracted_graph {
         assert!(key != v);
@@ -113,24 +211,52 @@ fn _check_graph_integrity(contracted_graph: &FxHashMap<u32, Vec<u32>>, v: u32) {
     }
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<(&str, &str, Vec<&str>)>> {
-    many1(terminated(
-        tuple((alpha1, tag(": "), separated_list1(space1, alpha1))),
-        opt(newline),
-    ))(input)
-}
-
 pub fn part_two(_: &str) -> Option<u32> {
     None
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
     use super::*;
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(54));
+        assert_eq!(result, Ok(54));
+    }
+
+    #[test]
+    fn test_karger_min_cut() {
+        let components = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let mut rng = StdRng::seed_from_u64(advent_of_code::random::BENCH_SEED);
+        let karger = karger_min_cut(&components, &mut rng).unwrap();
+        let stoer_wagner = components.min_cut().unwrap();
+
+        let sizes = |cut: &MinCut| cut.partitions.each_ref().map(Vec::len);
+        assert_eq!(karger.edges.len(), stoer_wagner.edges.len());
+        assert_eq!(sizes(&karger).iter().product::<usize>(), 54);
+        assert_eq!(sizes(&stoer_wagner).iter().product::<usize>(), 54);
+    }
+
+    #[test]
+    fn test_unexpected_cut() {
+        // Two triangles joined by a single wire
+        let result = part_one("a: b c\nb: c\nx: y z\ny: z\nc: x");
+        assert_eq!(
+            result.unwrap_err().message,
+            "expected to disconnect 3 wires, but the minimum cut has 1: c/x"
+        );
+        assert!(part_one("a: b").is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = part_one("jqt: rhn xhk\nrsh frs pzl\n").unwrap_err();
+        let location = error.location.unwrap();
+        assert_eq!((location.line, location.column), (2, 12));
+        assert_eq!(location.source_line, "rsh frs pzl");
     }
 }
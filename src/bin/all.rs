@@ -0,0 +1,81 @@
+//! Runs every solution in a single binary.
+//!
+//! This binary requires the `embed-inputs` feature, so it carries the inputs of every day and can be
+//! copied to another machine to compare timings: `cargo build --release --features embed-inputs --bin all`.
+use std::time::Instant;
+
+use advent_of_code::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[cfg(feature = "dhat-heap")]
+compile_error!("the `all` binary does not support `dhat-heap`, profile a single day instead.");
+
+macro_rules! days {
+    ($( $module:ident = $path:literal ),* $(,)?) => {
+        $(
+            #[allow(dead_code)]
+            #[path = $path]
+            mod $module;
+        )*
+
+        const DAYS: &[(advent_of_code::template::Day, fn() -> bool)] = &[
+            $( ($module::DAY, $module::run) ),*
+        ];
+    };
+}
+
+days!(
+    day01 = "01.rs",
+    day02 = "02.rs",
+    day03 = "03.rs",
+    day04 = "04.rs",
+    day05 = "05.rs",
+    day06 = "06.rs",
+    day07 = "07.rs",
+    day08 = "08.rs",
+    day09 = "09.rs",
+    day10 = "10.rs",
+    day11 = "11.rs",
+    day12 = "12.rs",
+    day13 = "13.rs",
+    day14 = "14.rs",
+    day15 = "15.rs",
+    day16 = "16.rs",
+    day17 = "17.rs",
+    day18 = "18.rs",
+    day19 = "19.rs",
+    day20 = "20.rs",
+    day21 = "21.rs",
+    day22 = "22.rs",
+    day23 = "23.rs",
+    day24 = "24.rs",
+    day25 = "25.rs",
+);
+
+fn main() {
+    let timer = Instant::now();
+    let mut failed = vec![];
+
+    for (i, (day, run)) in DAYS.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if !run() {
+            failed.push(day.to_string());
+        }
+    }
+
+    // when benching, the elapsed time includes all samples and is not meaningful.
+    if !std::env::args().any(|x| x == "--time") {
+        let elapsed = timer.elapsed();
+        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{elapsed:.2?}{ANSI_RESET}");
+    }
+
+    if !failed.is_empty() {
+        eprintln!("Failed day(s): {}", failed.join(", "));
+        std::process::exit(1);
+    }
+}
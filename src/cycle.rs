@@ -0,0 +1,240 @@
+//! Cycle detection for deterministic processes.
+//!
+//! When a puzzle asks for the state after an enormous number of iterations, the states usually
+//! start repeating long before. Once the first repeated state is found, any later iteration maps
+//! back to an earlier one with the same state.
+//!
+//! ```
+//! # use advent_of_code::cycle::{find_cycle, find_cycle_brent};
+//! // 0, 1, 2, 3, 4, 2, 3, 4, 2, ...
+//! let step = |n: &u32| if *n == 4 { 2 } else { n + 1 };
+//!
+//! let history = find_cycle(0, step);
+//! assert_eq!((history.cycle.start, history.cycle.length), (2, 3));
+//! assert_eq!(*history.state_at(1_000_000_000), 4);
+//!
+//! let cycle = find_cycle_brent(0, step);
+//! assert_eq!(cycle, history.cycle);
+//! assert_eq!(cycle.state_at(0, step, 1_000_000_000), 4);
+//! ```
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+/// Where the states of a process start repeating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first iteration whose state is part of the cycle.
+    pub start: usize,
+    /// The number of iterations before a state repeats.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest iteration with the same state as iteration `n`.
+    pub fn equivalent_iteration(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The state at iteration `n`, found by stepping from `initial` to the earliest equivalent
+    /// iteration.
+    pub fn state_at<S>(&self, initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        (0..self.equivalent_iteration(n)).fold(initial, |state, _| step(&state))
+    }
+}
+
+/// A cycle together with every state up to the end of its first repetition.
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state at iteration `n`, where iteration 0 is the initial state.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_iteration(n)]
+    }
+
+    /// The states of the iterations before the cycle repeats.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Steps from `initial` until a state repeats, remembering every state in a hash map.
+/// This needs one call of `step` per iteration, but keeps all the states in memory.
+pub fn find_cycle<S>(initial: S, step: impl FnMut(&S) -> S) -> History<S>
+where
+    S: Clone + Hash + Eq,
+{
+    find_cycle_by_key(initial, step, S::clone)
+}
+
+/// Like [`find_cycle`], but compares states by the key returned by `key`, which lets parts of
+/// the state that do not influence later iterations be ignored. Every state is still kept; use
+/// [`find_cycle_in_place`] to only keep the keys.
+pub fn find_cycle_by_key<S, K>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> History<S>
+where
+    K: Hash + Eq,
+{
+    let mut seen = FxHashMap::default();
+    let mut states = vec![initial];
+
+    loop {
+        let iteration = states.len() - 1;
+        let current = &states[iteration];
+        if let Some(start) = seen.insert(key(current), iteration) {
+            states.pop();
+            return History {
+                cycle: Cycle {
+                    start,
+                    length: iteration - start,
+                },
+                states,
+            };
+        }
+        let next = step(current);
+        states.push(next);
+    }
+}
+
+/// Steps `state` in place until its key repeats, keeping only the key and iteration of each
+/// state. Returns the cycle with `state` left at iteration `start + length`, which is the same as
+/// iteration `start`, so that stepping it `equivalent_iteration(n) - start` more times reaches the
+/// state at iteration `n`.
+pub fn find_cycle_in_place<S, K>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle
+where
+    K: Hash + Eq,
+{
+    let mut seen = FxHashMap::default();
+    for iteration in 0.. {
+        if let Some(start) = seen.insert(key(state), iteration) {
+            return Cycle {
+                start,
+                length: iteration - start,
+            };
+        }
+        step(state);
+    }
+    unreachable!()
+}
+
+/// Finds the cycle with Brent's algorithm, which only keeps two states in memory at a time.
+/// This calls `step` about three times as often as [`find_cycle`], so prefer it when states are
+/// large and cheap to compute.
+pub fn find_cycle_brent<S>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle
+where
+    S: Clone + Eq,
+{
+    // Find the cycle length by moving the tortoise to the hare at increasing powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare one cycle length ahead, both meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = (0..length).fold(initial, |state, _| step(&state));
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        // A linear congruential generator modulo 11 repeats immediately
+        let step = |n: &u64| (n * 7 + 3) % 11;
+        let history = find_cycle(1, step);
+        assert_eq!(history.cycle, find_cycle_brent(1, step));
+        assert_eq!(history.cycle.start, 0);
+        assert_eq!(history.states().len(), history.cycle.length);
+
+        for n in [0, 5, 10, 11, 12, 1000] {
+            let expected = (0..n).fold(1, |state, _| step(&state));
+            assert_eq!(*history.state_at(n), expected);
+            assert_eq!(history.cycle.state_at(1, step, n), expected);
+        }
+    }
+
+    #[test]
+    fn test_tail_before_cycle() {
+        // 10, 9, ..., 3, then 2, 1, 0, 2, 1, 0, ...
+        let step = |n: &i32| if *n == 0 { 2 } else { n - 1 };
+        let cycle = find_cycle_brent(10, step);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 8,
+                length: 3
+            }
+        );
+        assert_eq!(find_cycle(10, step).cycle, cycle);
+        assert_eq!(cycle.equivalent_iteration(7), 7);
+        assert_eq!(cycle.equivalent_iteration(14), 8);
+        assert_eq!(cycle.state_at(10, step, 14), 2);
+    }
+
+    #[test]
+    fn test_find_cycle_by_key() {
+        // The counter never repeats, but only the parity of the first element matters
+        let history = find_cycle_by_key((0, 0), |&(a, b)| (a + 1, b + 1), |(a, _)| a % 2);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        assert_eq!(history.state_at(5), &(1, 1));
+    }
+
+    #[test]
+    fn test_find_cycle_in_place() {
+        // 10, 9, ..., 3, then 2, 1, 0, 2, 1, 0, ...
+        let mut state = 10;
+        let cycle = find_cycle_in_place(
+            &mut state,
+            |n| *n = if *n == 0 { 2 } else { *n - 1 },
+            |n| *n,
+        );
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 8,
+                length: 3
+            }
+        );
+        assert_eq!(state, 2);
+    }
+}
@@ -0,0 +1,237 @@
+//! Exporting graphs to Graphviz DOT files.
+//!
+//! Drawing the graph behind a puzzle is often the quickest way to spot the structure a solution
+//! can rely on, such as a single node feeding the output, or a handful of edges joining two
+//! clusters. A [`Dot`] collects nodes and edges with their [`Style`], and renders to the DOT
+//! language for `dot`, `neato` or `sfdp` to lay out:
+//!
+//! ```
+//! # use advent_of_code::dot::{Dot, Style};
+//! let mut dot = Dot::digraph("modules");
+//! dot.node("broadcaster", Style::new().shape("doublecircle"));
+//! dot.edge("broadcaster", "a", Style::new().label("low"));
+//! assert_eq!(
+//!     dot.to_string(),
+//!     "digraph \"modules\" {\n  \"broadcaster\" [shape=\"doublecircle\"];\n  \"broadcaster\" -> \"a\" [label=\"low\"];\n}\n"
+//! );
+//! ```
+//!
+//! A day exports its graph by naming a function from the input to a [`Dot`] in the
+//! [`solution!`](crate::solution) macro, which `cargo solve NN --graph` then writes to
+//! `data/graph/NN.dot` instead of solving the puzzle.
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::graph::{Graph, NodeId};
+use crate::template::Day;
+
+/// The attributes of a node, an edge or the whole graph, such as its label or colour.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    attributes: Vec<(String, String)>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets any attribute, replacing an earlier value for the same key.
+    pub fn set(mut self, key: &str, value: impl Display) -> Self {
+        let value = value.to_string();
+        match self.attributes.iter_mut().find(|(k, _)| k == key) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((key.to_string(), value)),
+        }
+        self
+    }
+
+    pub fn label(self, label: impl Display) -> Self {
+        self.set("label", label)
+    }
+
+    pub fn color(self, color: &str) -> Self {
+        self.set("color", color)
+    }
+
+    pub fn shape(self, shape: &str) -> Self {
+        self.set("shape", shape)
+    }
+
+    /// Sets the line style, such as `bold`, `dashed` or `filled`.
+    pub fn style(self, style: &str) -> Self {
+        self.set("style", style)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+/// Writes ` [key="value", ...]`, or nothing for an empty style.
+impl Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let attributes: Vec<String> = self
+            .attributes
+            .iter()
+            .map(|(key, value)| format!("{key}={}", quote(value)))
+            .collect();
+        write!(f, " [{}]", attributes.join(", "))
+    }
+}
+
+/// Quotes an id or value, so that any name is valid.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A graph to export, with its nodes and edges in the order they were added.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    style: Style,
+    nodes: Vec<(String, Style)>,
+    edges: Vec<(String, String, Style)>,
+}
+
+impl Dot {
+    /// A graph whose edges have a direction, drawn as arrows.
+    pub fn digraph(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    /// A graph whose edges can be followed both ways.
+    pub fn graph(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            style: Style::new(),
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Exports a library [`Graph`], styling each node by its id and each edge by its ends.
+    /// Nodes are named as in the graph; undirected edges should be exported with `directed` false.
+    pub fn from_graph(
+        name: &str,
+        graph: &Graph,
+        directed: bool,
+        mut node_style: impl FnMut(NodeId) -> Style,
+        mut edge_style: impl FnMut(NodeId, NodeId) -> Style,
+    ) -> Self {
+        let mut dot = Self::new(name, directed);
+        for id in 0..graph.len() {
+            dot.node(graph.name(id), node_style(id));
+        }
+        for &(from, to) in graph.edges() {
+            dot.edge(graph.name(from), graph.name(to), edge_style(from, to));
+        }
+        dot
+    }
+
+    /// Sets the attributes of the whole graph, such as `rankdir` or `layout`.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Adds a node with a style. Nodes that only appear in edges don't need to be added.
+    pub fn node(&mut self, id: &str, style: Style) {
+        self.nodes.push((id.to_string(), style));
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, style: Style) {
+        self.edges.push((from.to_string(), to.to_string(), style));
+    }
+
+    /// Writes the graph to `data/graph/NN.dot`, or `data/graph/NN-<input>.dot` for an input other
+    /// than the primary one, named after the file stem of the input. Returns the path written.
+    pub fn write(&self, day: Day, input: Option<&str>) -> io::Result<PathBuf> {
+        let dir = PathBuf::from("./data/graph");
+        fs::create_dir_all(&dir)?;
+        let path = match input {
+            Some(input) => {
+                let stem = Path::new(input).file_stem().unwrap_or(input.as_ref());
+                dir.join(format!("{day}-{}.dot", stem.to_string_lossy()))
+            }
+            None => dir.join(format!("{day}.dot")),
+        };
+        fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, arrow) = match self.directed {
+            true => ("digraph", "->"),
+            false => ("graph", "--"),
+        };
+        writeln!(f, "{kind} {} {{", quote(&self.name))?;
+        if !self.style.is_empty() {
+            writeln!(f, "  graph{};", self.style)?;
+        }
+        for (id, style) in &self.nodes {
+            writeln!(f, "  {}{style};", quote(id))?;
+        }
+        for (from, to, style) in &self.edges {
+            writeln!(f, "  {} {arrow} {}{style};", quote(from), quote(to))?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style() {
+        let style = Style::new().color("red").label("a \"b\"").color("blue");
+        assert_eq!(
+            style.to_string(),
+            " [color=\"blue\", label=\"a \\\"b\\\"\"]"
+        );
+        assert_eq!(Style::new().to_string(), "");
+    }
+
+    #[test]
+    fn test_from_graph() {
+        let mut graph = Graph::new();
+        graph.add_undirected_edge("a", "b");
+        graph.add_undirected_edge("b", "c");
+
+        let dot = Dot::from_graph(
+            "g",
+            &graph,
+            false,
+            |id| match graph.name(id) {
+                "a" => Style::new().shape("box"),
+                _ => Style::new(),
+            },
+            |from, _| match from {
+                0 => Style::new().color("red"),
+                _ => Style::new(),
+            },
+        )
+        .with_style(Style::new().set("layout", "neato"));
+
+        assert_eq!(
+            dot.to_string(),
+            "graph \"g\" {\n  graph [layout=\"neato\"];\n  \"a\" [shape=\"box\"];\n  \"b\";\n  \"c\";\n  \"a\" -- \"b\" [color=\"red\"];\n  \"b\" -- \"c\";\n}\n"
+        );
+    }
+}
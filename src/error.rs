@@ -0,0 +1,141 @@
+//! Errors returned by solutions in place of an answer.
+//!
+//! A part can return `Result<T, Error>` instead of `Option<T>`. Parse errors convert with `?`,
+//! keeping the position of the problem, and the runner adds the day before showing them:
+//!
+//! ```text
+//! Part 1: ✖
+//! error: invalid number "x"
+//!  --> day 04, line 3, column 12
+//!   |
+//! 3 | Card 3:  1 x 53 | 69 82 63
+//!   |            ^
+//! ```
+use std::fmt::Display;
+
+use crate::parse::ParseError;
+use crate::template::Day;
+
+/// Where in the input an [`Error`] happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// The line, counted from 1.
+    pub line: usize,
+    /// The column, counted in characters from 1.
+    pub column: usize,
+    /// The text of the line, or empty if it is not known.
+    pub source_line: String,
+}
+
+/// A solution failure, optionally pointing at the input that caused it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    pub day: Option<Day>,
+    pub location: Option<Location>,
+}
+
+/// The result of a solution part that can fail.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// An error that is not tied to a position in the input, such as an input without an answer.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            day: None,
+            location: None,
+        }
+    }
+
+    /// Records the day whose solution failed.
+    pub fn with_day(mut self, day: Day) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self {
+            message: error.message,
+            day: None,
+            location: Some(Location {
+                line: error.line,
+                column: error.column,
+                source_line: error.source_line,
+            }),
+        }
+    }
+}
+
+impl From<aoc_parse::ParseError> for Error {
+    fn from(error: aoc_parse::ParseError) -> Self {
+        ParseError::from(error).into()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "error: {}", self.message)?;
+
+        let position = self
+            .location
+            .as_ref()
+            .map(|l| format!("line {}, column {}", l.line, l.column));
+        let at = match (self.day, position) {
+            (Some(day), Some(position)) => format!("day {day}, {position}"),
+            (Some(day), None) => format!("day {day}"),
+            (None, Some(position)) => position,
+            (None, None) => return Ok(()),
+        };
+        let Some(location) = self.location.as_ref().filter(|l| !l.source_line.is_empty()) else {
+            return write!(f, "\n --> {at}");
+        };
+
+        // Point a caret at the column, below the line of input
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+        write!(
+            f,
+            "\n{gutter}--> {at}\n{gutter} |\n{number} | {}\n{gutter} | {}^",
+            location.source_line,
+            " ".repeat(location.column - 1)
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error: Error = crate::parse::numbers::<u32>("1 x 3").unwrap_err().into();
+        assert_eq!(
+            error.to_string(),
+            "error: invalid number \"x\"\n --> line 1, column 3\n  |\n1 | 1 x 3\n  |   ^"
+        );
+
+        let error = Error::from(ParseError::at_offset(
+            "1 2\n3 x\n",
+            6,
+            "invalid number \"x\"",
+        ))
+        .with_day(Day::new(4).unwrap());
+        assert_eq!(
+            error.to_string(),
+            "error: invalid number \"x\"\n --> day 04, line 2, column 3\n  |\n2 | 3 x\n  |   ^"
+        );
+
+        assert_eq!(Error::new("no answer").to_string(), "error: no answer");
+        assert_eq!(
+            Error::new("no answer")
+                .with_day(Day::new(25).unwrap())
+                .to_string(),
+            "error: no answer\n --> day 25"
+        );
+    }
+}
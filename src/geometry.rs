@@ -0,0 +1,473 @@
+//! Integer points and compass directions.
+//!
+//! Coordinates follow the puzzle maps: `x` grows to the right and `y` grows downwards, so
+//! [`Direction::Up`] has the offset `(0, -1)`. This matches the positions used by
+//! [`Grid`](crate::grid::Grid).
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or vector on a 2D plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The number of horizontal plus vertical steps between two points.
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Iterates over the four orthogonal neighbours, clockwise starting from the one above.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d.offset())
+    }
+
+    /// Iterates over the eight orthogonal and diagonal neighbours, clockwise starting from the one
+    /// above.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |d| self + d.offset())
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A point or vector in 3D space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The sum of the absolute differences along each axis.
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Adds two vectors, or returns `None` if any coordinate overflows.
+    pub fn checked_add(self, other: Point3) -> Option<Point3> {
+        Some(Point3::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+            self.z.checked_add(other.z)?,
+        ))
+    }
+
+    /// Subtracts two vectors, or returns `None` if any coordinate overflows.
+    pub fn checked_sub(self, other: Point3) -> Option<Point3> {
+        Some(Point3::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+            self.z.checked_sub(other.z)?,
+        ))
+    }
+
+    /// Scales the vector, or returns `None` if any coordinate overflows.
+    pub fn checked_mul(self, factor: i64) -> Option<Point3> {
+        Some(Point3::new(
+            self.x.checked_mul(factor)?,
+            self.y.checked_mul(factor)?,
+            self.z.checked_mul(factor)?,
+        ))
+    }
+
+    /// The dot product, or `None` if it overflows.
+    pub fn checked_dot(self, other: Point3) -> Option<i64> {
+        self.x
+            .checked_mul(other.x)?
+            .checked_add(self.y.checked_mul(other.y)?)?
+            .checked_add(self.z.checked_mul(other.z)?)
+    }
+
+    /// The cross product, perpendicular to both vectors, or `None` if it overflows.
+    pub fn checked_cross(self, other: Point3) -> Option<Point3> {
+        let minor =
+            |a: i64, b: i64, c: i64, d: i64| a.checked_mul(b)?.checked_sub(c.checked_mul(d)?);
+        Some(Point3::new(
+            minor(self.y, other.z, self.z, other.y)?,
+            minor(self.z, other.x, self.x, other.z)?,
+            minor(self.x, other.y, self.y, other.x)?,
+        ))
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Implements the vector arithmetic shared by [`Point`] and [`Point3`].
+macro_rules! impl_vector_ops {
+    ($t:ident { $($field:ident),+ }) => {
+        impl Add for $t {
+            type Output = $t;
+
+            fn add(self, rhs: $t) -> $t {
+                $t { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $t {
+            type Output = $t;
+
+            fn sub(self, rhs: $t) -> $t {
+                $t { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $t {
+            type Output = $t;
+
+            fn mul(self, rhs: i64) -> $t {
+                $t { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                $t { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $t {
+            fn add_assign(&mut self, rhs: $t) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, rhs: $t) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Point { x, y });
+impl_vector_ops!(Point3 { x, y, z });
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a direction from a character.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(pub char);
+
+impl Error for ParseDirectionError {}
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a direction", self.0)
+    }
+}
+
+/// One of the four cardinal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The direction after turning 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The direction after turning 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The unit vector pointing in this direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Parses one of `U`, `D`, `L` and `R`.
+    pub fn from_udlr(c: char) -> Result<Self, ParseDirectionError> {
+        match c {
+            'U' => Ok(Direction::Up),
+            'R' => Ok(Direction::Right),
+            'D' => Ok(Direction::Down),
+            'L' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+
+    /// Parses one of the compass points `N`, `E`, `S` and `W`, where north is up.
+    pub fn from_nsew(c: char) -> Result<Self, ParseDirectionError> {
+        match c {
+            'N' => Ok(Direction::Up),
+            'E' => Ok(Direction::Right),
+            'S' => Ok(Direction::Down),
+            'W' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+
+    /// Parses one of the arrows `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: char) -> Result<Self, ParseDirectionError> {
+        match c {
+            '^' => Ok(Direction::Up),
+            '>' => Ok(Direction::Right),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+
+    /// Parses a digit `0` to `3` meaning right, down, left and up, as used by the hexadecimal
+    /// instructions of day 18.
+    pub fn from_digit(c: char) -> Result<Self, ParseDirectionError> {
+        match c {
+            '0' => Ok(Direction::Right),
+            '1' => Ok(Direction::Down),
+            '2' => Ok(Direction::Left),
+            '3' => Ok(Direction::Up),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+
+    /// The arrow pointing in this direction.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+/// Parses any of the letter or arrow encodings. Digits are not accepted, as their order differs
+/// between puzzles, use [`Direction::from_digit`] instead.
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_udlr(c)
+            .or_else(|_| Self::from_nsew(c))
+            .or_else(|_| Self::from_arrow(c))
+    }
+}
+
+/// One of the eight cardinal and diagonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting from [`Direction8::Up`].
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// The direction after turning 45° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The direction after turning 45° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The vector pointing in this direction, with a length of one along each axis.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction8::Up => Point::new(0, -1),
+            Direction8::UpRight => Point::new(1, -1),
+            Direction8::Right => Point::new(1, 0),
+            Direction8::DownRight => Point::new(1, 1),
+            Direction8::Down => Point::new(0, 1),
+            Direction8::DownLeft => Point::new(-1, 1),
+            Direction8::Left => Point::new(-1, 0),
+            Direction8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Parses one of the compass points `N`, `NE`, `E`, `SE`, `S`, `SW`, `W` and `NW`.
+    pub fn from_compass(s: &str) -> Option<Self> {
+        let direction = match s {
+            "N" => Direction8::Up,
+            "NE" => Direction8::UpRight,
+            "E" => Direction8::Right,
+            "SE" => Direction8::DownRight,
+            "S" => Direction8::Down,
+            "SW" => Direction8::DownLeft,
+            "W" => Direction8::Left,
+            "NW" => Direction8::UpLeft,
+            _ => return None,
+        };
+        Some(direction)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        Self::ALL[value as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, 11);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a + b, Point::new(6, 17));
+        assert_eq!(b - a, Point::new(4, 5));
+        assert_eq!(-a * 2, Point::new(-2, -12));
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(-1, 2, 5)), 4);
+    }
+
+    #[test]
+    fn test_checked_point3() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, 5, 6);
+        assert_eq!(a.checked_add(b), Some(a + b));
+        assert_eq!(a.checked_sub(b), Some(a - b));
+        assert_eq!(a.checked_mul(3), Some(a * 3));
+        assert_eq!(a.checked_dot(b), Some(32));
+        assert_eq!(a.checked_cross(b), Some(Point3::new(-3, 6, -3)));
+
+        let big = Point3::new(i64::MAX, 0, 0);
+        assert_eq!(big.checked_add(a), None);
+        assert_eq!(big.checked_mul(2), None);
+        assert_eq!(big.checked_dot(big), None);
+        assert_eq!(
+            Point3::new(0, i64::MAX, 1).checked_cross(Point3::new(0, 2, 3)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let neighbours = Point::ORIGIN.neighbours4().collect::<Vec<_>>();
+        assert_eq!(
+            neighbours,
+            vec![
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(Point::new(3, 3).neighbours8().count(), 8);
+    }
+
+    #[test]
+    fn test_turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.offset() + d.opposite().offset(), Point::ORIGIN);
+            assert_eq!(Direction8::from(d).offset(), d.offset());
+        }
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::UpRight.opposite(), Direction8::DownLeft);
+        assert!(Direction8::DownLeft.is_diagonal());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Direction::from_udlr('L'), Ok(Direction::Left));
+        assert_eq!(Direction::from_digit('1'), Ok(Direction::Down));
+        assert_eq!(Direction::try_from('N'), Ok(Direction::Up));
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert_eq!(Direction::try_from('x'), Err(ParseDirectionError('x')));
+        assert_eq!(Direction8::from_compass("SE"), Some(Direction8::DownRight));
+        for d in Direction::ALL {
+            assert_eq!(Direction::from_arrow(d.arrow()), Ok(d));
+        }
+    }
+}
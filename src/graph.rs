@@ -0,0 +1,333 @@
+//! Graphs of named nodes, stored as adjacency lists.
+//!
+//! Node names are interned into dense ids on insertion, so the algorithms work on plain indices
+//! and never have to hash strings.
+//!
+//! ```
+//! # use advent_of_code::graph::Graph;
+//! // Two triangles joined by a single edge
+//! let mut graph = Graph::new();
+//! for (a, b) in [("a", "b"), ("b", "c"), ("c", "a"), ("x", "y"), ("y", "z"), ("z", "x"), ("c", "x")] {
+//!     graph.add_undirected_edge(a, b);
+//! }
+//! let cut = graph.min_cut().unwrap();
+//! assert_eq!(cut.edges.len(), 1);
+//! assert_eq!(cut.partitions[0].len() * cut.partitions[1].len(), 9);
+//! ```
+use std::hash::BuildHasherDefault;
+
+use priority_queue::PriorityQueue;
+use rustc_hash::{FxHashMap, FxHasher};
+
+/// The index of a node in a [`Graph`].
+pub type NodeId = usize;
+
+/// Assigns each distinct name a dense id, in the order the names are first seen.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    ids: FxHashMap<String, NodeId>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `name`, assigning the next id if it has not been seen before.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    /// Returns the id of `name` if it has been interned.
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of an interned id.
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A global minimum cut, splitting the nodes of a graph into two non-empty sets so that as few
+/// edges as possible connect them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinCut {
+    /// The edges connecting the two partitions, as added to the graph.
+    pub edges: Vec<(NodeId, NodeId)>,
+    pub partitions: [Vec<NodeId>; 2],
+}
+
+/// A graph with directed and undirected edges between named nodes. Parallel edges are kept.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    nodes: Interner,
+    adjacency: Vec<Vec<NodeId>>,
+    edges: Vec<(NodeId, NodeId)>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of the node called `name`, adding it if it does not exist yet.
+    pub fn add_node(&mut self, name: &str) -> NodeId {
+        let id = self.nodes.intern(name);
+        if id == self.adjacency.len() {
+            self.adjacency.push(vec![]);
+        }
+        id
+    }
+
+    /// Adds an edge that can only be followed from `from` to `to`, adding the nodes as needed.
+    pub fn add_edge(&mut self, from: &str, to: &str) -> (NodeId, NodeId) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.adjacency[from].push(to);
+        self.edges.push((from, to));
+        (from, to)
+    }
+
+    /// Adds an edge that can be followed both ways, adding the nodes as needed.
+    pub fn add_undirected_edge(&mut self, a: &str, b: &str) -> (NodeId, NodeId) {
+        let (a, b) = self.add_edge(a, b);
+        self.adjacency[b].push(a);
+        (a, b)
+    }
+
+    /// Returns the id of the node called `name`, if it exists.
+    pub fn node(&self, name: &str) -> Option<NodeId> {
+        self.nodes.get(name)
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        self.nodes.name(id)
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// The nodes that can be reached from `id` by following a single edge.
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        &self.adjacency[id]
+    }
+
+    /// Every edge in the order it was added. Undirected edges appear once.
+    pub fn edges(&self) -> &[(NodeId, NodeId)] {
+        &self.edges
+    }
+
+    /// Groups the nodes into sets connected by edges, ignoring the direction of edges.
+    /// Components are ordered by their smallest node id and list their nodes in ascending order.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        // Union-find with path halving
+        let mut parent: Vec<NodeId> = (0..self.len()).collect();
+        fn find(parent: &mut [NodeId], mut id: NodeId) -> NodeId {
+            while parent[id] != id {
+                parent[id] = parent[parent[id]];
+                id = parent[id];
+            }
+            id
+        }
+
+        for &(a, b) in &self.edges {
+            let (a, b) = (find(&mut parent, a), find(&mut parent, b));
+            parent[a.max(b)] = a.min(b);
+        }
+
+        let mut components: Vec<Vec<NodeId>> = vec![];
+        let mut component_of_root = FxHashMap::default();
+        for id in 0..self.len() {
+            let root = find(&mut parent, id);
+            let index = *component_of_root.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[index].push(id);
+        }
+        components
+    }
+
+    /// Finds a global minimum cut with the Stoer–Wagner algorithm, ignoring the direction of
+    /// edges. The result is deterministic for a given graph.
+    ///
+    /// Returns `None` if the graph has fewer than two nodes.
+    pub fn min_cut(&self) -> Option<MinCut> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        // Edge weights between the merged groups of nodes
+        let mut weights: Vec<FxHashMap<NodeId, u64>> = vec![FxHashMap::default(); n];
+        for &(a, b) in &self.edges {
+            if a != b {
+                *weights[a].entry(b).or_default() += 1;
+                *weights[b].entry(a).or_default() += 1;
+            }
+        }
+        let mut groups: Vec<Vec<NodeId>> = (0..n).map(|id| vec![id]).collect();
+        let mut active: Vec<NodeId> = (0..n).collect();
+        let mut best: Option<(u64, Vec<NodeId>)> = None;
+
+        while active.len() > 1 {
+            // Maximum adjacency search: repeatedly add the node most tightly connected to the
+            // nodes added so far. The last two nodes added are the least connected.
+            let mut queue =
+                PriorityQueue::<NodeId, u64, BuildHasherDefault<FxHasher>>::with_default_hasher();
+            for &id in &active {
+                queue.push(id, 0);
+            }
+            let (mut previous, mut last, mut cut_weight) = (active[0], active[0], 0);
+            while let Some((id, weight)) = queue.pop() {
+                (previous, last, cut_weight) = (last, id, weight);
+                for (&other, &w) in &weights[id] {
+                    queue.change_priority_by(&other, |p| *p += w);
+                }
+            }
+
+            // The cut of the phase separates the last node from everything else
+            if best.as_ref().is_none_or(|(w, _)| cut_weight < *w) {
+                best = Some((cut_weight, groups[last].clone()));
+            }
+
+            // Merge the last node into the previous one
+            let merged = std::mem::take(&mut weights[last]);
+            for (other, w) in merged {
+                weights[other].remove(&last);
+                if other != previous {
+                    *weights[previous].entry(other).or_default() += w;
+                    *weights[other].entry(previous).or_default() += w;
+                }
+            }
+            let group = std::mem::take(&mut groups[last]);
+            groups[previous].extend(group);
+            active.retain(|&id| id != last);
+        }
+
+        let (_, side) = best?;
+        let mut in_side = vec![false; n];
+        for &id in &side {
+            in_side[id] = true;
+        }
+
+        let edges = self
+            .edges
+            .iter()
+            .copied()
+            .filter(|&(a, b)| in_side[a] != in_side[b])
+            .collect();
+        let (mut first, mut second): (Vec<NodeId>, Vec<NodeId>) =
+            (0..n).partition(|&id| in_side[id]);
+        first.sort_unstable();
+        second.sort_unstable();
+
+        Some(MinCut {
+            edges,
+            partitions: [first, second],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interner() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("jqt"), 0);
+        assert_eq!(interner.intern("rhn"), 1);
+        assert_eq!(interner.intern("jqt"), 0);
+        assert_eq!(interner.get("rhn"), Some(1));
+        assert_eq!(interner.get("xhk"), None);
+        assert_eq!(interner.name(1), "rhn");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut graph = Graph::new();
+        let (a, b) = graph.add_edge("a", "b");
+        graph.add_undirected_edge("b", "c");
+        let c = graph.node("c").unwrap();
+        assert_eq!(graph.neighbours(a), &[b]);
+        assert_eq!(graph.neighbours(b), &[c]);
+        assert_eq!(graph.neighbours(c), &[b]);
+        assert_eq!(graph.edges().len(), 2);
+        assert_eq!(graph.name(c), "c");
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("c", "d");
+        graph.add_edge("e", "d");
+        graph.add_node("f");
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![0, 1], vec![2, 3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_min_cut() {
+        // Two complete graphs of four nodes joined by two edges
+        let mut graph = Graph::new();
+        for side in ["a", "b"] {
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    graph.add_undirected_edge(&format!("{side}{i}"), &format!("{side}{j}"));
+                }
+            }
+        }
+        graph.add_undirected_edge("a0", "b0");
+        graph.add_undirected_edge("a1", "b2");
+
+        let cut = graph.min_cut().unwrap();
+        let mut cut_names = cut
+            .edges
+            .iter()
+            .map(|&(a, b)| (graph.name(a), graph.name(b)))
+            .collect::<Vec<_>>();
+        cut_names.sort_unstable();
+        assert_eq!(cut_names, vec![("a0", "b0"), ("a1", "b2")]);
+        assert_eq!(cut.partitions[0].len(), 4);
+        assert_eq!(cut.partitions[1].len(), 4);
+        assert_eq!(graph.min_cut(), Some(cut));
+    }
+
+    #[test]
+    fn test_min_cut_disconnected() {
+        let mut graph = Graph::new();
+        graph.add_undirected_edge("a", "b");
+        graph.add_undirected_edge("c", "d");
+        let cut = graph.min_cut().unwrap();
+        assert!(cut.edges.is_empty());
+        assert_eq!(cut.partitions[0].len() + cut.partitions[1].len(), 4);
+
+        assert_eq!(Graph::new().min_cut(), None);
+    }
+}
@@ -0,0 +1,415 @@
+//! A two-dimensional grid of cells, as used by the map-based puzzles.
+//!
+//! Cells are stored row by row in a single vector. Positions are `(x, y)` pairs, where `x` is the
+//! column and `y` is the row counted from the top.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+/// Lines and columns are counted from 1.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A row is not as wide as the first row.
+    RaggedRow { line: usize, width: usize },
+    /// The cell-mapping closure rejected a character.
+    InvalidCell { line: usize, column: usize, c: char },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::RaggedRow { line, width } => {
+                write!(f, "line {line}: expected a row of width {width}")
+            }
+            ParseGridError::InvalidCell { line, column, c } => {
+                write!(
+                    f,
+                    "line {line}, column {column}: unexpected character {c:?}"
+                )
+            }
+        }
+    }
+}
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells stored row by row.
+    ///
+    /// # Panics
+    /// Panics if the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {width}",
+            cells.len()
+        );
+        Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses a character map, converting each character with `f`. Empty lines are skipped.
+    ///
+    /// # Panics
+    /// Panics if the rows are not all of the same width.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(input, |c| Some(f(c))).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Parses a character map, converting each character with `f`, which returns `None` for
+    /// characters that are not allowed. Empty lines are skipped.
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut width = 0;
+        let mut cells = vec![];
+
+        for (line, row) in input.lines().enumerate() {
+            if row.is_empty() {
+                continue;
+            }
+
+            let row_start = cells.len();
+            for (column, c) in row.chars().enumerate() {
+                let cell = f(c).ok_or(ParseGridError::InvalidCell {
+                    line: line + 1,
+                    column: column + 1,
+                    c,
+                })?;
+                cells.push(cell);
+            }
+
+            let row_width = cells.len() - row_start;
+            if width == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(ParseGridError::RaggedRow {
+                    line: line + 1,
+                    width,
+                });
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Whether the signed position lies inside the grid.
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Returns the cell at a signed position, or `None` if it lies outside the grid.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.contains(x, y)
+            .then(|| &self.cells[y as usize * self.width + x as usize])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at a signed position on a grid that repeats infinitely in every direction.
+    ///
+    /// Panics if the grid is empty, as there is no cell to repeat.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// Iterates over the positions of the up to four orthogonal neighbours of a position inside
+    /// the grid, clockwise starting from the one above.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &OFFSETS_4)
+    }
+
+    /// Iterates over the positions of the up to eight orthogonal and diagonal neighbours of a
+    /// position inside the grid, clockwise starting from the top left.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &OFFSETS_8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            self.contains(nx, ny).then_some((nx as usize, ny as usize))
+        })
+    }
+
+    /// Iterates over all positions and their cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    /// Returns the position of the first cell, row by row, matching the predicate.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // an empty grid, such as one parsed from empty input, has no cells to split
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Panics if `x` is outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "x {x} is outside of width {}", self.width);
+        self.cells[x..].iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Converts every cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Returns a grid with rows and columns swapped.
+    pub fn transposed(&self) -> Self {
+        self.rearranged(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Returns the grid rotated by 90° clockwise.
+    pub fn rotated_clockwise(&self) -> Self {
+        self.rearranged(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// Returns the grid rotated by 90° counter-clockwise.
+    pub fn rotated_counter_clockwise(&self) -> Self {
+        self.rearranged(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// Builds a grid of the given size, where `source` maps each new position to the
+    /// position of the cell it is copied from.
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = source(x, y);
+                self.cells[sy * self.width + sx].clone()
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(x < self.width, "x {x} is outside of width {}", self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(x < self.width, "x {x} is outside of width {}", self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c)
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        assert_eq!(
+            Grid::try_parse("ab\nabc", Some),
+            Err(ParseGridError::RaggedRow { line: 2, width: 2 })
+        );
+        assert_eq!(
+            Grid::try_parse(".#\n.x", |c| match c {
+                '.' => Some(false),
+                '#' => Some(true),
+                _ => None,
+            }),
+            Err(ParseGridError::InvalidCell {
+                line: 2,
+                column: 2,
+                c: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid = Grid::try_parse("", Some).unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.to_string(), "");
+        assert_eq!(Grid::new(0, 0, '.').to_string(), "");
+    }
+
+    #[test]
+    fn test_indexing() {
+        let grid = example();
+        assert_eq!(grid.get(0, 0), Some(&'a'));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get_wrapping(-1, 0), &'c');
+        assert_eq!(grid.get_wrapping(4, 5), &'e');
+        assert_eq!(grid.position(|c| *c == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbours4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8(1, 0).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = example();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.transposed().to_string(), "ad\nbe\ncf\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "x 3 is outside of width 3")]
+    fn test_column_outside() {
+        let _ = example().column(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_wrapping_empty() {
+        Grid::<char>::new(0, 0, '.').get_wrapping(0, 0);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let grid = example();
+        assert_eq!(grid.rotated_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotated_counter_clockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotated_clockwise().rotated_counter_clockwise(), grid);
+    }
+}
@@ -0,0 +1,345 @@
+//! Intervals of integers and the sets and mappings built from them.
+//!
+//! Puzzles such as day 5 and day 19 pass huge ranges of numbers through a series of rules. Rather
+//! than tracking each number, the ranges are split wherever a rule applies and processed whole.
+//!
+//! ```
+//! # use advent_of_code::interval::{Interval, RangeMap};
+//! // Numbers from 10 to 19 move up by 100, everything else stays where it is
+//! let mut map = RangeMap::new();
+//! map.insert(Interval::new(10, 20), 100);
+//! assert_eq!(map.map(15), 115);
+//! assert_eq!(
+//!     map.map_interval(Interval::new(5, 15)),
+//!     vec![Interval::new(110, 115), Interval::new(5, 10)]
+//! );
+//! ```
+use std::fmt::Display;
+use std::ops::Range;
+
+/// The half-open interval `start..end`. An interval with `end <= start` is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The interval `start..=last`.
+    pub const fn inclusive(start: i64, last: i64) -> Self {
+        Self::new(start, last + 1)
+    }
+
+    /// The interval of `len` numbers beginning at `start`.
+    pub const fn with_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    /// The number of integers in the interval.
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The numbers in both intervals, or `None` if they do not overlap.
+    pub fn intersect(&self, other: Interval) -> Option<Interval> {
+        let result = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// The numbers of this interval that are not in `other`, as up to two non-empty intervals
+    /// below and above it.
+    pub fn subtract(&self, other: Interval) -> impl Iterator<Item = Interval> {
+        let below = Interval::new(self.start, self.end.min(other.start));
+        let above = Interval::new(self.start.max(other.end), self.end);
+        [below, above].into_iter().filter(|i| !i.is_empty())
+    }
+
+    /// Splits the interval into the numbers below `value` and the numbers from `value` onwards.
+    /// Either part may be empty.
+    pub fn split_at(&self, value: i64) -> (Interval, Interval) {
+        let value = value.clamp(self.start, self.end.max(self.start));
+        (
+            Interval::new(self.start, value),
+            Interval::new(value, self.end),
+        )
+    }
+
+    /// Moves the interval by `offset`.
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(value: Range<i64>) -> Self {
+        Self::new(value.start, value.end)
+    }
+}
+
+impl From<Interval> for Range<i64> {
+    fn from(value: Interval) -> Self {
+        value.start..value.end
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// The number of integer points inside a box with one interval per dimension.
+pub fn box_volume(dimensions: &[Interval]) -> i64 {
+    dimensions.iter().map(Interval::len).product()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of integers, stored as sorted and disjoint intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the numbers of an interval, merging it with any intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    /// Removes the numbers of an interval.
+    pub fn remove(&mut self, interval: Interval) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| i.subtract(interval))
+            .collect();
+    }
+
+    /// The numbers of the set that lie within `interval`.
+    pub fn intersect(&self, interval: Interval) -> IntervalSet {
+        Self {
+            intervals: self
+                .intervals
+                .iter()
+                .filter_map(|i| i.intersect(interval))
+                .collect(),
+        }
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    /// The smallest number in the set.
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// The largest number in the set.
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|i| i.end - 1)
+    }
+
+    /// Iterates over the disjoint intervals in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    fn normalize(&mut self) {
+        self.intervals.retain(|i| !i.is_empty());
+        self.intervals.sort_unstable();
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = Self {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise mapping of integers, where each source interval is shifted by its own offset and
+/// numbers outside every source interval map to themselves.
+///
+/// If source intervals overlap, the one inserted first applies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    pieces: Vec<(Interval, i64)>,
+}
+
+impl RangeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the numbers of `source` to `source` shifted by `offset`.
+    pub fn insert(&mut self, source: Interval, offset: i64) {
+        self.pieces.push((source, offset));
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(_, offset)| value + offset)
+    }
+
+    /// Maps every number of an interval, splitting it wherever a different piece applies.
+    /// The mapped intervals are returned in the order of the pieces, followed by the unmapped
+    /// parts of the interval.
+    pub fn map_interval(&self, interval: Interval) -> Vec<Interval> {
+        let mut mapped = vec![];
+        let mut unmapped = vec![interval];
+
+        for (source, offset) in &self.pieces {
+            unmapped = unmapped
+                .into_iter()
+                .flat_map(|i| {
+                    if let Some(common) = i.intersect(*source) {
+                        mapped.push(common.shift(*offset));
+                    }
+                    i.subtract(*source)
+                })
+                .collect();
+        }
+
+        mapped.extend(unmapped.into_iter().filter(|i| !i.is_empty()));
+        mapped
+    }
+
+    /// Maps every number of a set.
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.iter().flat_map(|i| self.map_interval(*i)).collect()
+    }
+}
+
+impl FromIterator<(Interval, i64)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (Interval, i64)>>(iter: I) -> Self {
+        Self {
+            pieces: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(10, 20);
+        assert_eq!(a.len(), 10);
+        assert_eq!(Interval::inclusive(1, 4000).len(), 4000);
+        assert_eq!(Interval::new(5, 3).len(), 0);
+        assert_eq!(
+            a.intersect(Interval::new(15, 30)),
+            Some(Interval::new(15, 20))
+        );
+        assert_eq!(a.intersect(Interval::new(20, 30)), None);
+        assert_eq!(
+            a.subtract(Interval::new(12, 14)).collect::<Vec<_>>(),
+            vec![Interval::new(10, 12), Interval::new(14, 20)]
+        );
+        assert_eq!(a.subtract(Interval::new(0, 30)).count(), 0);
+        assert_eq!(
+            a.split_at(15),
+            (Interval::new(10, 15), Interval::new(15, 20))
+        );
+        assert!(a.split_at(5).0.is_empty());
+        assert!(a.split_at(25).1.is_empty());
+        assert_eq!(a.shift(-10), Interval::new(0, 10));
+        assert_eq!(
+            box_volume(&[
+                Interval::new(0, 2),
+                Interval::new(0, 3),
+                Interval::new(1, 5)
+            ]),
+            24
+        );
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let mut set: IntervalSet = [
+            Interval::new(5, 8),
+            Interval::new(0, 3),
+            Interval::new(3, 4),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(0, 4), Interval::new(5, 8)]
+        );
+        assert_eq!(set.len(), 7);
+
+        set.insert(Interval::new(4, 5));
+        assert_eq!(set.iter().count(), 1);
+
+        set.remove(Interval::new(2, 6));
+        assert_eq!(set.len(), 4);
+        assert!(!set.contains(3));
+        assert_eq!((set.min(), set.max()), (Some(0), Some(7)));
+        assert_eq!(set.intersect(Interval::new(1, 7)).len(), 2);
+    }
+
+    #[test]
+    fn test_range_map() {
+        let map: RangeMap = [(Interval::new(98, 100), -48), (Interval::new(50, 98), 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(14), 14);
+
+        let set = map.map_set(&IntervalSet::from_iter([Interval::new(90, 110)]));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(50, 52), Interval::new(92, 110)]
+        );
+    }
+}
@@ -1,3 +1,22 @@
+pub mod cycle;
+pub mod dot;
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod maze;
+pub mod memo;
+pub mod number_theory;
+pub mod ocr;
+pub mod parse;
+pub mod polygon;
+pub mod random;
+pub mod search;
+pub mod sequence;
+pub mod space;
 pub mod template;
+pub mod trace;
+pub mod vis;
 
 // Use this file to add helper functions and additional modules.
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, progress, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -26,6 +28,10 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            part: Option<u8>,
+            /// Flags passed on to the solution unchanged.
+            flags: Vec<String>,
         },
         All {
             release: bool,
@@ -35,6 +41,12 @@ mod args {
             day: Option<Day>,
             store: bool,
         },
+        Progress {
+            store: bool,
+        },
+        Verify {
+            day: Option<Day>,
+        },
         #[cfg(feature = "today")]
         Today,
     }
@@ -42,7 +54,7 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let mut app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
@@ -56,6 +68,12 @@ mod args {
                     store,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                day: args.opt_free_from_str()?,
+            },
+            Some("progress") => AppArguments::Progress {
+                store: args.contains("--store"),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -71,6 +89,13 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                input: args.opt_value_from_str("--input")?,
+                part: args.opt_value_from_str("--part")?,
+                flags: ["--vis", "--graph", "--trace"]
+                    .into_iter()
+                    .filter(|flag| args.contains(*flag))
+                    .map(String::from)
+                    .collect(),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -84,7 +109,26 @@ mod args {
             }
         };
 
-        let remaining = args.finish();
+        // the seed of randomised solutions takes a value, so it is passed on with it
+        if let AppArguments::Solve { flags, .. } = &mut app_args {
+            if let Some(seed) = args.opt_value_from_str::<_, u64>("--seed")? {
+                flags.extend(["--seed".to_string(), seed.to_string()]);
+            }
+        }
+
+        let mut remaining = args.finish();
+
+        // pico-args only matches `--trace` on its own, so pass on `--trace=<level>` from the rest
+        if let AppArguments::Solve { flags, .. } = &mut app_args {
+            remaining.retain(|arg| match arg.to_str() {
+                Some(arg) if arg.starts_with("--trace=") => {
+                    flags.push(arg.to_string());
+                    false
+                }
+                _ => true,
+            });
+        }
+
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
@@ -102,6 +146,8 @@ fn main() {
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
             AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Progress { store } => progress::handle(store),
+            AppArguments::Verify { day } => verify::handle(day),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day, download } => {
@@ -115,7 +161,10 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                part,
+                flags,
+            } => solve::handle(day, release, dhat, submit, input, part, &flags),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
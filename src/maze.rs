@@ -0,0 +1,358 @@
+//! Contraction of grid mazes into graphs of junctions, and the longest simple path between two
+//! junctions.
+//!
+//! Mazes like the hiking trails of day 23 are mostly long corridors. Replacing each corridor by a
+//! single weighted edge leaves a graph of a few dozen junctions, which is small enough to search
+//! exhaustively with the visited junctions kept in a bitmask.
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::thread;
+
+use rustc_hash::FxHashMap;
+
+use crate::geometry::Direction;
+use crate::grid::Grid;
+
+/// The id of a junction in a [`JunctionGraph`].
+pub type JunctionId = usize;
+
+/// The junctions of a maze with the corridors connecting them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JunctionGraph {
+    positions: Vec<(usize, usize)>,
+    edges: Vec<Vec<(JunctionId, u32)>>,
+}
+
+/// Branches to search ahead of time when splitting the longest path search across threads.
+const BRANCHES_PER_THREAD: usize = 16;
+
+impl JunctionGraph {
+    /// Contracts the maze of `grid` into a graph.
+    ///
+    /// `can_enter(cell, direction)` returns whether a cell can be entered by moving in
+    /// `direction`. Walls never can; one-way tiles such as slopes only in their direction.
+    ///
+    /// The junctions are `start`, `end` and every cell with at least three enterable neighbours,
+    /// with ids assigned in that order and then row by row. Each corridor between two junctions
+    /// becomes an edge weighted by its length, in every direction it can be walked.
+    pub fn contract<T>(
+        grid: &Grid<T>,
+        start: (usize, usize),
+        end: (usize, usize),
+        can_enter: impl Fn(&T, Direction) -> bool,
+    ) -> Self {
+        let neighbour = |(x, y): (usize, usize), direction: Direction| {
+            let offset = direction.offset();
+            let (nx, ny) = (
+                x as isize + offset.x as isize,
+                y as isize + offset.y as isize,
+            );
+            grid.contains(nx, ny).then_some((nx as usize, ny as usize))
+        };
+        let is_open = |position: (usize, usize)| {
+            Direction::ALL
+                .into_iter()
+                .any(|d| can_enter(&grid[position], d))
+        };
+        let open_neighbours = |position| {
+            Direction::ALL
+                .into_iter()
+                .filter_map(|d| neighbour(position, d))
+                .filter(|&n| is_open(n))
+                .count()
+        };
+
+        let mut positions = vec![start, end];
+        positions.extend(
+            grid.iter()
+                .map(|(position, _)| position)
+                .filter(|&p| p != start && p != end && is_open(p) && open_neighbours(p) >= 3),
+        );
+        let ids: FxHashMap<(usize, usize), JunctionId> = positions
+            .iter()
+            .enumerate()
+            .map(|(id, &position)| (position, id))
+            .collect();
+
+        let mut edges = vec![vec![]; positions.len()];
+        for (from, &junction) in positions.iter().enumerate() {
+            for direction in Direction::ALL {
+                // Follow the corridor until it reaches another junction, a dead end, or a tile
+                // that cannot be entered from this side
+                let mut heading = direction;
+                let mut current = junction;
+                let mut distance = 0;
+                let to = loop {
+                    let next =
+                        neighbour(current, heading).filter(|&n| can_enter(&grid[n], heading));
+                    let Some(next) = next else {
+                        break None;
+                    };
+                    current = next;
+                    distance += 1;
+
+                    if let Some(&id) = ids.get(&current) {
+                        break Some(id);
+                    }
+
+                    let onwards = Direction::ALL.into_iter().find(|&d| {
+                        d != heading.opposite() && neighbour(current, d).is_some_and(is_open)
+                    });
+                    match onwards {
+                        Some(d) => heading = d,
+                        None => break None,
+                    }
+                };
+
+                if let Some(to) = to.filter(|&to| to != from) {
+                    edges[from].push((to, distance));
+                }
+            }
+        }
+
+        crate::trace!(
+            Debug,
+            "contracted the maze to {} junctions and {} edges",
+            positions.len(),
+            edges.iter().map(Vec::len).sum::<usize>()
+        );
+        Self { positions, edges }
+    }
+
+    /// The number of junctions.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The grid position of a junction.
+    pub fn position(&self, id: JunctionId) -> (usize, usize) {
+        self.positions[id]
+    }
+
+    /// The junctions reachable from `id` through a single corridor, with the corridor lengths.
+    pub fn edges(&self, id: JunctionId) -> &[(JunctionId, u32)] {
+        &self.edges[id]
+    }
+
+    /// The length of the longest path from `from` to `to` that visits no junction twice, or
+    /// `None` if `to` cannot be reached.
+    ///
+    /// # Panics
+    /// Panics if the graph has more than 64 junctions.
+    pub fn longest_path(&self, from: JunctionId, to: JunctionId) -> Option<u32> {
+        self.longest_path_parallel(from, to, 1)
+    }
+
+    /// Like [`JunctionGraph::longest_path`], but searches the branches near `from` on up to
+    /// `threads` threads.
+    pub fn longest_path_parallel(
+        &self,
+        from: JunctionId,
+        to: JunctionId,
+        threads: usize,
+    ) -> Option<u32> {
+        assert!(
+            self.len() <= 64,
+            "visited junctions are tracked in a 64 bit mask, but there are {}",
+            self.len()
+        );
+
+        let search = LongestPath::new(self, to);
+        let start = Branch {
+            junction: from,
+            visited: 1 << from,
+            distance: 0,
+            remaining: search.max_edges.iter().sum(),
+        };
+
+        if threads <= 1 {
+            search.search(start);
+        } else {
+            // Expand the first levels of the search breadth-first until there are enough
+            // branches to share between the threads
+            let mut branches = vec![start];
+            while !branches.is_empty() && branches.len() < threads * BRANCHES_PER_THREAD {
+                let expanded: Vec<Branch> = branches
+                    .iter()
+                    .flat_map(|branch| search.expand(branch))
+                    .collect();
+                if expanded.is_empty() {
+                    break;
+                }
+                branches = expanded;
+            }
+
+            let next = AtomicUsize::new(0);
+            thread::scope(|scope| {
+                for _ in 0..threads {
+                    scope.spawn(|| {
+                        while let Some(branch) = branches.get(next.fetch_add(1, Ordering::Relaxed))
+                        {
+                            search.search(*branch);
+                        }
+                    });
+                }
+            });
+        }
+
+        let best = search.best.load(Ordering::Relaxed);
+        (best >= 0).then_some(best as u32)
+    }
+}
+
+/// A partial path of the longest path search.
+#[derive(Clone, Copy, Debug)]
+struct Branch {
+    junction: JunctionId,
+    visited: u64,
+    distance: u32,
+    /// An upper bound on the distance still to come: the longest edge leaving each junction that
+    /// has not been left yet.
+    remaining: u32,
+}
+
+struct LongestPath<'a> {
+    graph: &'a JunctionGraph,
+    to: JunctionId,
+    max_edges: Vec<u32>,
+    /// If `to` can only be reached from one junction, a path reaching that junction must go
+    /// straight to `to`, as it could never come back.
+    last_junction: Option<JunctionId>,
+    best: AtomicI64,
+}
+
+impl<'a> LongestPath<'a> {
+    fn new(graph: &'a JunctionGraph, to: JunctionId) -> Self {
+        let max_edges = graph
+            .edges
+            .iter()
+            .map(|edges| edges.iter().map(|(_, d)| *d).max().unwrap_or(0))
+            .collect();
+
+        let mut into_to =
+            (0..graph.len()).filter(|&id| graph.edges[id].iter().any(|(n, _)| *n == to));
+        let last_junction = match (into_to.next(), into_to.next()) {
+            (Some(id), None) => Some(id),
+            _ => None,
+        };
+
+        Self {
+            graph,
+            to,
+            max_edges,
+            last_junction,
+            best: AtomicI64::new(-1),
+        }
+    }
+
+    /// The branches one step further than `branch`. A branch that reaches the destination
+    /// updates the best distance instead.
+    fn expand(&self, branch: &Branch) -> Vec<Branch> {
+        if branch.junction == self.to {
+            self.best
+                .fetch_max(i64::from(branch.distance), Ordering::Relaxed);
+            return vec![];
+        }
+
+        let remaining = branch.remaining - self.max_edges[branch.junction];
+        self.graph.edges[branch.junction]
+            .iter()
+            .filter(|(next, _)| {
+                branch.visited & (1 << next) == 0
+                    && (self.last_junction != Some(branch.junction) || *next == self.to)
+            })
+            .map(|&(next, d)| Branch {
+                junction: next,
+                visited: branch.visited | (1 << next),
+                distance: branch.distance + d,
+                remaining,
+            })
+            .collect()
+    }
+
+    fn search(&self, branch: Branch) {
+        if branch.junction == self.to {
+            self.best
+                .fetch_max(i64::from(branch.distance), Ordering::Relaxed);
+            return;
+        }
+        // Stop if even the longest conceivable continuation can't beat the best path found
+        if i64::from(branch.distance + branch.remaining) <= self.best.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let remaining = branch.remaining - self.max_edges[branch.junction];
+        for &(next, d) in &self.graph.edges[branch.junction] {
+            if branch.visited & (1 << next) != 0
+                || (self.last_junction == Some(branch.junction) && next != self.to)
+            {
+                continue;
+            }
+            self.search(Branch {
+                junction: next,
+                visited: branch.visited | (1 << next),
+                distance: branch.distance + d,
+                remaining,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+#.#####
+#.....#
+#.###.#
+#...>.#
+###.#.#
+###...#
+#####.#
+";
+
+    fn contract(slippery: bool) -> JunctionGraph {
+        let grid = Grid::parse(MAZE, |c| c);
+        JunctionGraph::contract(&grid, (1, 0), (5, 6), |&c, d| match c {
+            '#' => false,
+            '>' if slippery => d == Direction::Right,
+            _ => true,
+        })
+    }
+
+    #[test]
+    fn test_contract() {
+        let graph = contract(false);
+        // start, end and the junctions at (1, 1), (3, 3), (5, 3) and (5, 5)
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.position(2), (1, 1));
+        assert_eq!(graph.position(4), (5, 3));
+        assert_eq!(graph.edges(0), &[(2, 1)]);
+        assert!(graph.edges(2).contains(&(4, 6)));
+        assert!(graph.edges(3).contains(&(4, 2)));
+        assert!(graph.edges(4).contains(&(3, 2)));
+
+        // The slope can't be climbed from the right
+        let graph = contract(true);
+        assert!(graph.edges(3).contains(&(4, 2)));
+        assert!(!graph.edges(4).contains(&(3, 2)));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let graph = contract(false);
+        assert_eq!(graph.longest_path(0, 1), Some(14));
+        assert_eq!(graph.longest_path_parallel(0, 1, 4), Some(14));
+        assert_eq!(graph.longest_path(1, 0), Some(14));
+        assert_eq!(graph.longest_path(0, 0), Some(0));
+
+        let graph = contract(true);
+        assert_eq!(graph.longest_path(0, 1), Some(10));
+        assert_eq!(graph.longest_path_parallel(0, 1, 4), Some(10));
+        assert_eq!(graph.longest_path(1, 0), Some(14));
+    }
+}
@@ -0,0 +1,194 @@
+//! Memoization for recursive solvers.
+//!
+//! A [`Memo`] owns the cache that would otherwise be threaded through the recursion by hand. The
+//! computation is a closure that receives the memo back, so it can recurse through it:
+//!
+//! ```
+//! # use advent_of_code::memo::Memo;
+//! fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//!     memo.get_or_compute(&n, |memo| match n {
+//!         0 | 1 => n,
+//!         _ => fibonacci(n - 1, memo) + fibonacci(n - 2, memo),
+//!     })
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+//! ```
+//!
+//! Keys can be looked up by a borrowed form, such as a `&[i32]` for a `Vec<i32>` key, and are
+//! only copied into the cache when a value is computed. In debug builds the memo also counts its
+//! hits and misses, to check that the key actually gets reused.
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+/// How often a [`Memo`] found a value in its cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoStats {
+    /// The fraction of lookups that were answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// A cache of computed values by key.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: FxHashMap<K, V>,
+    #[cfg(debug_assertions)]
+    stats: MemoStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: FxHashMap::default(),
+            #[cfg(debug_assertions)]
+            stats: MemoStats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `key`, or computes it with `compute` and caches it.
+    ///
+    /// `compute` is given the memo so it can make recursive calls. It must not depend on anything
+    /// other than the key, or the cached values will be wrong for later lookups.
+    pub fn get_or_compute<Q>(&mut self, key: &Q, compute: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value) = self.cache.get(key) {
+            #[cfg(debug_assertions)]
+            {
+                self.stats.hits += 1;
+            }
+            return value.clone();
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            self.stats.misses += 1;
+        }
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    /// The cached value for `key`, if it has been computed.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get(key)
+    }
+
+    /// The number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets every cached value, so the memo can be reused for an unrelated problem.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// The hits and misses so far. Only counted in debug builds, so this is `None` in release
+    /// builds.
+    pub fn stats(&self) -> Option<MemoStats> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.stats)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_paths(width: u64, height: u64, memo: &mut Memo<(u64, u64), u64>) -> u64 {
+        memo.get_or_compute(&(width, height), |memo| {
+            if width == 0 || height == 0 {
+                1
+            } else {
+                count_paths(width - 1, height, memo) + count_paths(width, height - 1, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(count_paths(16, 16, &mut memo), 601080390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        assert_eq!(memo.get(&(2, 2)), Some(&6));
+
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        fn sum_suffixes(values: &[i32], memo: &mut Memo<Vec<i32>, i32>) -> i32 {
+            memo.get_or_compute(values, |memo| match values {
+                [] => 0,
+                [first, rest @ ..] => first + sum_suffixes(rest, memo),
+            })
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(sum_suffixes(&[1, 2, 3], &mut memo), 6);
+        assert_eq!(memo.get([2, 3].as_slice()), Some(&5));
+        assert_eq!(sum_suffixes(&[4, 2, 3], &mut memo), 9);
+        assert_eq!(memo.len(), 5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_stats() {
+        let mut memo = Memo::new();
+        count_paths(2, 2, &mut memo);
+        let stats = memo.stats().unwrap();
+        assert_eq!(stats.misses, 8);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.to_string(), "1 hits, 8 misses (11.1% hit rate)");
+    }
+}
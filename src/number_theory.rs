@@ -0,0 +1,166 @@
+//! Greatest common divisors, least common multiples and the Chinese remainder theorem.
+//!
+//! These come up when several independent cycles have to line up, such as the ghosts of day 8 or
+//! the conjunction gates of day 20. If every cycle starts at time zero, the answer is simply the
+//! least common multiple of the cycle lengths. [`first_coincidence`] handles cycles with
+//! arbitrary offsets and lengths that share factors.
+//!
+//! ```
+//! # use advent_of_code::number_theory::{first_coincidence, lcm_all};
+//! assert_eq!(lcm_all([4, 6, 10]), Some(60));
+//! // Cycles seen at 3, 7, 11, ... and at 5, 11, 17, ... first line up at 11
+//! assert_eq!(first_coincidence([(3, 4), (5, 6)]), Some(11));
+//! // Cycles seen at even and odd times never line up
+//! assert_eq!(first_coincidence([(0, 2), (1, 4)]), None);
+//! ```
+use num::Integer;
+
+/// The greatest common divisor of all values, or `None` if there are none.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().reduce(|acc, v| acc.gcd(&v))
+}
+
+/// The least common multiple of all values, or `None` if there are none.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().reduce(|acc, v| acc.lcm(&v))
+}
+
+/// Returns `(g, x, y)` where `g` is the non-negative greatest common divisor of `a` and `b`, and
+/// `a * x + b * y == g`.
+///
+/// Returns `None` if the result does not fit in an `i64`, which only happens when the divisor is
+/// 2^63, as for `extended_gcd(i64::MIN, 0)`.
+pub fn extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    let (g, x, y) = extended_gcd_i128(a.into(), b.into());
+    Some((g.try_into().ok()?, x.try_into().ok()?, y.try_into().ok()?))
+}
+
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solves a system of congruences `t ≡ residue (mod modulus)`, given as `(residue, modulus)`
+/// pairs with positive moduli. The moduli do not need to be coprime.
+///
+/// Returns `(t, m)`, where `m` is the least common multiple of the moduli and `0 <= t < m`, so
+/// the solutions are exactly `t + k * m`. Returns `None` if the congruences contradict each
+/// other.
+///
+/// # Panics
+/// Panics if a modulus is not positive, or if the least common multiple does not fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut result: (i128, i128) = (0, 1);
+
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive, got {modulus}");
+        let (r1, m1) = result;
+        let (r2, m2) = (i128::from(residue), i128::from(modulus));
+
+        // Find k such that r1 + k * m1 ≡ r2 (mod m2), which needs g to divide the difference
+        let (g, x, _) = extended_gcd_i128(m1, m2);
+        let diff = r2 - r1;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let m2_g = m2 / g;
+        let k = (diff / g % m2_g * (x % m2_g)).rem_euclid(m2_g);
+        let lcm = m1 * m2_g;
+        assert!(
+            lcm <= i128::from(i64::MAX),
+            "least common multiple of the moduli overflows i64"
+        );
+        result = ((r1 + k * m1).rem_euclid(lcm), lcm);
+    }
+
+    Some((result.0 as i64, result.1 as i64))
+}
+
+/// Finds the first time at which several periodic events happen together. Each event is given as
+/// `(offset, period)`: it first happens at `offset`, and then every `period` after that.
+///
+/// Returns `None` if the events never coincide.
+pub fn first_coincidence(events: impl IntoIterator<Item = (i64, i64)>) -> Option<i64> {
+    let events: Vec<(i64, i64)> = events.into_iter().collect();
+    let (t, m) = crt(events.iter().copied())?;
+
+    // Every event must have happened at least once, so move past the latest first occurrence
+    let earliest = events.iter().map(|(offset, _)| *offset).max().unwrap_or(0);
+    if t >= earliest {
+        Some(t)
+    } else {
+        Some(t + Integer::div_ceil(&(earliest - t), &m) * m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd_all([12, 18, 30]), Some(6));
+        assert_eq!(lcm_all([2u64, 3, 4]), Some(12));
+        assert_eq!(lcm_all(Vec::<u32>::new()), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 5), (0, 7), (7, 0), (-3, -9)] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(g, a.gcd(&b));
+            assert_eq!(a * x + b * y, g);
+        }
+
+        // The divisor of these is 2^63
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), None);
+        // but other results with i64::MIN fit
+        let (g, x, y) = extended_gcd(i64::MIN, 6).unwrap();
+        assert_eq!(g, 2);
+        assert_eq!(i128::from(i64::MIN) * i128::from(x) + 6 * i128::from(y), 2);
+    }
+
+    #[test]
+    fn test_crt() {
+        // Coprime moduli
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli sharing a factor
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        // Negative residues are normalized
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        // Large moduli do not overflow intermediate products
+        let primes = [1_000_003, 1_000_033, 1_000_037];
+        let (t, m) = crt(primes.map(|p| (p - 1, p))).unwrap();
+        assert_eq!(m, primes.iter().product::<i64>());
+        assert_eq!(t, m - 1);
+    }
+
+    #[test]
+    fn test_first_coincidence() {
+        // Cycles starting at zero coincide at the least common multiple
+        assert_eq!(first_coincidence([(3, 3), (4, 4)]), Some(12));
+        // Offsets smaller than the period
+        assert_eq!(first_coincidence([(1, 3), (2, 4)]), Some(10));
+        // An event that only begins after the solution of the congruences
+        assert_eq!(first_coincidence([(0, 2), (14, 4)]), Some(14));
+        assert_eq!(first_coincidence([(0, 2), (1, 2)]), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Reading capital letters drawn in pixels.
+//!
+//! Some puzzles answer with text drawn as `#` and `.` pixels rather than printed. The letters
+//! come from two fonts, 4 pixels wide by 6 high and 6 wide by 10 high, and only the letters that
+//! have appeared in puzzles are known. Letters are told apart by the empty columns between them,
+//! so the art doesn't need to be aligned to a fixed width:
+//!
+//! ```
+//! # use advent_of_code::ocr;
+//! let art = [
+//!     "#..#.####.#....#.....##.",
+//!     "#..#.#....#....#....#..#",
+//!     "####.###..#....#....#..#",
+//!     "#..#.#....#....#....#..#",
+//!     "#..#.#....#....#....#..#",
+//!     "#..#.####.####.####..##.",
+//! ];
+//! assert_eq!(ocr::decode(&art.join("\n")).as_deref(), Some("HELLO"));
+//! ```
+use crate::grid::Grid;
+
+/// The letters of the font 4 pixels wide (some are narrower or wider) and 6 high.
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.|#..#|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|#..#|###."),
+    ('C', ".##.|#..#|#...|#...|#..#|.##."),
+    ('E', "####|#...|###.|#...|#...|####"),
+    ('F', "####|#...|###.|#...|#...|#..."),
+    ('G', ".##.|#..#|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#|#..#"),
+    ('I', "###|.#.|.#.|.#.|.#.|###"),
+    ('J', "..##|...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|#...|####"),
+    ('O', ".##.|#..#|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|#..#|###.|#...|#..."),
+    ('R', "###.|#..#|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|#...|.##.|...#|###."),
+    ('U', "#..#|#..#|#..#|#..#|#..#|.##."),
+    ('Y', "#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|..#.|.#..|#...|####"),
+];
+
+/// The letters of the font 6 pixels wide and 10 high.
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..|.#..#.|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'B',
+        "#####.|#....#|#....#|#....#|#####.|#....#|#....#|#....#|#....#|#####.",
+    ),
+    (
+        'C',
+        ".####.|#....#|#.....|#.....|#.....|#.....|#.....|#.....|#....#|.####.",
+    ),
+    (
+        'E',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'F',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'G',
+        ".####.|#....#|#.....|#.....|#.....|#..###|#....#|#....#|#...##|.###.#",
+    ),
+    (
+        'H',
+        "#....#|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'J',
+        "...###|....#.|....#.|....#.|....#.|....#.|....#.|#...#.|#...#.|.###..",
+    ),
+    (
+        'K',
+        "#....#|#...#.|#..#..|#.#...|##....|##....|#.#...|#..#..|#...#.|#....#",
+    ),
+    (
+        'L',
+        "#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'N',
+        "#....#|##...#|##...#|#.#..#|#.#..#|#..#.#|#..#.#|#...##|#...##|#....#",
+    ),
+    (
+        'P',
+        "#####.|#....#|#....#|#....#|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'R',
+        "#####.|#....#|#....#|#....#|#####.|#..#..|#...#.|#...#.|#....#|#....#",
+    ),
+    (
+        'X',
+        "#....#|#....#|.#..#.|.#..#.|..##..|..##..|.#..#.|.#..#.|#....#|#....#",
+    ),
+    (
+        'Z',
+        "######|.....#|.....#|....#.|...#..|..#...|.#....|#.....|#.....|######",
+    ),
+];
+
+/// Whether a character is a lit pixel. Besides `#`, solutions sometimes draw with blocks.
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Reads the letters drawn in text, with `#` or `█` for lit pixels and `.` or a space for the
+/// rest. Blank lines around the art are ignored.
+///
+/// Returns `None` if the art is not 6 or 10 pixels high, or has a shape that isn't a known
+/// letter.
+pub fn decode(art: &str) -> Option<String> {
+    let rows: Vec<&str> = art
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|row| row.is_empty())
+        .collect();
+    let height = rows.iter().rposition(|row| !row.is_empty())? + 1;
+    let rows = &rows[..height];
+    if rows
+        .iter()
+        .any(|row| row.chars().any(|c| !is_lit(c) && c != '.' && c != ' '))
+    {
+        return None;
+    }
+
+    let width = rows.iter().map(|row| row.chars().count()).max()?;
+    let pixels = (0..height)
+        .flat_map(|y| {
+            let mut row: Vec<bool> = rows[y].chars().map(is_lit).collect();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+    decode_grid(&Grid::from_vec(width, pixels))
+}
+
+/// Reads the letters drawn in a grid of pixels, where `true` is lit. See [`decode`].
+pub fn decode_grid(pixels: &Grid<bool>) -> Option<String> {
+    let font: &[(char, &str)] = match pixels.height() {
+        6 => &SMALL,
+        10 => &LARGE,
+        _ => return None,
+    };
+
+    // Split into letters at the columns without any lit pixels
+    let lit_columns: Vec<bool> = pixels.columns().map(|mut c| c.any(|&p| p)).collect();
+    let mut text = String::new();
+    let mut x = 0;
+    while x < lit_columns.len() {
+        if !lit_columns[x] {
+            x += 1;
+            continue;
+        }
+        let end = (x..lit_columns.len())
+            .find(|&i| !lit_columns[i])
+            .unwrap_or(lit_columns.len());
+        let shape = (0..pixels.height())
+            .map(|y| {
+                pixels.row(y)[x..end]
+                    .iter()
+                    .map(|&p| if p { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        text.push(letter(font, &shape)?);
+        x = end;
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Finds the letter with a shape, comparing only the columns that have lit pixels.
+fn letter(font: &[(char, &str)], shape: &str) -> Option<char> {
+    font.iter()
+        .find(|(_, glyph)| trim_columns(glyph) == shape)
+        .map(|&(c, _)| c)
+}
+
+/// Removes the columns of a glyph without any lit pixels from both sides.
+fn trim_columns(glyph: &str) -> String {
+    let rows: Vec<&str> = glyph.split('|').collect();
+    let lit = |x: usize| rows.iter().any(|row| row.as_bytes()[x] == b'#');
+    let width = rows[0].len();
+    let start = (0..width).find(|&x| lit(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| lit(x)).map_or(width, |x| x + 1);
+    rows.iter()
+        .map(|row| &row[start..end])
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws text in a font, with gaps between the letters.
+    fn draw(font: &[(char, &str)], text: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                font.iter()
+                    .find(|&&(l, _)| l == c)
+                    .unwrap()
+                    .1
+                    .split('|')
+                    .collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_fonts() {
+        let small: String = SMALL.iter().map(|&(c, _)| c).collect();
+        assert_eq!(decode(&draw(&SMALL, &small, 1)), Some(small));
+
+        let large: String = LARGE.iter().map(|&(c, _)| c).collect();
+        assert_eq!(decode(&draw(&LARGE, &large, 2)), Some(large));
+    }
+
+    #[test]
+    fn test_formats() {
+        // Blocks and spaces, surrounded by blank lines
+        let art = draw(&SMALL, "FJ", 3).replace('#', "█").replace('.', " ");
+        assert_eq!(decode(&format!("\n{art}\n\n")).as_deref(), Some("FJ"));
+    }
+
+    #[test]
+    fn test_not_letters() {
+        assert_eq!(decode("12345"), None);
+        assert_eq!(decode("#..#\n####"), None);
+        assert_eq!(decode(&draw(&SMALL, "AB", 1).replacen('#', ".", 1)), None);
+        assert_eq!(decode(&".".repeat(10)), None);
+    }
+}
@@ -0,0 +1,398 @@
+//! Helpers for the shapes puzzle inputs keep coming in, with errors that point at the input.
+//!
+//! Each helper parses the text it is given and reports problems by line and column within that
+//! text. [`lines`] and [`Section::parse`] shift the positions reported by the parser they call, so
+//! nested helpers still point at the right place in the whole input. Errors from `nom` and
+//! `aoc-parse` parsers can be converted with [`finish`] and `?` respectively.
+//!
+//! ```
+//! # use advent_of_code::parse::{lines, mapping, numbers, sections};
+//! let input = "3 -1, 4\n\nbroadcaster -> a, b\n&inv -> x\n";
+//! let [first, second] = sections(input).try_into().unwrap();
+//! assert_eq!(numbers::<i32>(first.text).unwrap(), vec![3, -1, 4]);
+//!
+//! let modules = second.parse(|text| lines(text, |line| mapping(line, " -> "))).unwrap();
+//! assert_eq!(modules[1], ("&inv", vec!["x"]));
+//!
+//! let error = numbers::<u8>("1 2\n3 x 5").unwrap_err();
+//! assert_eq!((error.line, error.column), (2, 3));
+//! assert_eq!(error.to_string(), "line 2, column 3: invalid number \"x\"");
+//! ```
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{i64, space0, space1},
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::grid::{Grid, ParseGridError};
+
+/// A problem with the input, at a line and column counted from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    /// The text of the line with the problem, if known.
+    pub source_line: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+            source_line: String::new(),
+        }
+    }
+
+    /// An error at a byte offset into `source`.
+    pub fn at_offset(source: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = source[..line_start].matches('\n').count() + 1;
+        let column = source[line_start..offset].chars().count() + 1;
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        Self {
+            source_line: source[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            ..Self::new(line, column, message)
+        }
+    }
+
+    /// An error at the start of `fragment`, which must be a slice of `source`. A fragment from
+    /// elsewhere is reported at the start of `source`.
+    pub fn at(source: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+        Self::at_offset(
+            source,
+            if offset <= source.len() { offset } else { 0 },
+            message,
+        )
+    }
+
+    /// Moves an error found while parsing `fragment`, which must be a slice of `source`, to its
+    /// position in `source`.
+    pub fn within(mut self, source: &str, fragment: &str) -> Self {
+        let start = Self::at(source, fragment, "");
+        if self.line == 1 {
+            self.column += start.column - 1;
+            self.source_line = start.source_line;
+        }
+        self.line += start.line - 1;
+        self
+    }
+
+    /// Moves the error down by `lines`, for an error found in text that started part way through
+    /// the input.
+    pub fn shift_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseGridError> for ParseError {
+    fn from(error: ParseGridError) -> Self {
+        match error {
+            ParseGridError::RaggedRow { line, .. } => Self::new(line, 1, error.to_string()),
+            ParseGridError::InvalidCell { line, column, .. } => {
+                Self::new(line, column, error.to_string())
+            }
+        }
+    }
+}
+
+impl From<aoc_parse::ParseError> for ParseError {
+    fn from(error: aoc_parse::ParseError) -> Self {
+        // The message ends with a location of its own, which is replaced by ours
+        let message = error.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(m, _)| m);
+        Self::at_offset(&error.source, error.location, message)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses the numbers in `text`, separated by whitespace, commas or both.
+pub fn numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| ParseError::at(text, token, format!("invalid number {token:?}")))
+        })
+        .collect()
+}
+
+/// Parses each line of `input` with `parse`, ignoring a final newline. Errors from `parse` are
+/// moved to the line they were found on.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.shift_lines(i)))
+        .collect()
+}
+
+/// A block of lines from [`sections`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub text: &'a str,
+    /// The line of the input the section starts on, counted from 1.
+    pub line: usize,
+}
+
+impl<'a> Section<'a> {
+    /// Parses the section, moving errors from `parse` to the line of the input they were found
+    /// on.
+    pub fn parse<T>(
+        &self,
+        parse: impl FnOnce(&'a str) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        parse(self.text).map_err(|e| e.shift_lines(self.line - 1))
+    }
+}
+
+/// Splits `input` into the blocks of lines separated by blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let blank = line.trim().is_empty();
+        match (start, blank) {
+            (None, false) => start = Some((offset, i + 1)),
+            (Some((from, line)), true) => {
+                sections.push(Section {
+                    text: input[from..offset].trim_end_matches(['\n', '\r']),
+                    line,
+                });
+                start = None;
+            }
+            _ => (),
+        }
+        offset += line.len();
+    }
+    if let Some((from, line)) = start {
+        sections.push(Section {
+            text: input[from..].trim_end_matches(['\n', '\r']),
+            line,
+        });
+    }
+    sections
+}
+
+/// Parses a line like `name -> a, b`, given the separator between the name and its targets.
+/// Targets are separated by commas, whitespace or both, and may be wrapped in parentheses like
+/// `AAA = (BBB, CCC)`.
+pub fn mapping<'a>(line: &'a str, separator: &str) -> Result<(&'a str, Vec<&'a str>), ParseError> {
+    let Some((name, targets)) = line.split_once(separator) else {
+        return Err(ParseError::at_offset(
+            line,
+            line.len(),
+            format!("expected {separator:?}"),
+        ));
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ParseError::at_offset(line, 0, "expected a name"));
+    }
+
+    let targets = targets.trim();
+    let targets = targets
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .unwrap_or(targets);
+    let targets: Vec<&str> = targets
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|target| !target.is_empty())
+        .collect();
+    if targets.is_empty() {
+        return Err(ParseError::at_offset(
+            line,
+            line.len(),
+            format!("expected targets after {separator:?}"),
+        ));
+    }
+    Ok((name, targets))
+}
+
+/// Parses a grid of characters, converting each with `cell`. See [`Grid::try_parse`].
+pub fn char_grid<T>(
+    input: &str,
+    cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, ParseError> {
+    Grid::try_parse(input, cell).map_err(|e| {
+        let error = ParseError::from(e);
+        let source_line = input.lines().nth(error.line - 1).unwrap_or("").to_string();
+        ParseError {
+            source_line,
+            ..error
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A `nom` parser for a list of integers separated by commas, spaces or both.
+pub fn number_list(input: &str) -> IResult<&str, Vec<i64>> {
+    separated_list1(alt((tag(","), space1)), tuple((space0, i64)))(input)
+        .map(|(rest, list)| (rest, list.into_iter().map(|(_, n)| n).collect()))
+}
+
+/// Finishes a `nom` parse of `source`, turning a failure into an error at the place it
+/// happened. Anything but whitespace left over after the parser is an error too.
+pub fn finish<'a, T>(source: &'a str, result: IResult<&'a str, T>) -> Result<T, ParseError> {
+    match result {
+        Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+        Ok((rest, _)) => Err(ParseError::at(source, rest, "unexpected input")),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::at(
+            source,
+            e.input,
+            format!("expected {}", e.code.description()),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at_offset(
+            source,
+            source.len(),
+            "unexpected end of input",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{character::complete::newline, multi::many1, sequence::terminated};
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers::<i64>(" 1,2, -3\n4 "), Ok(vec![1, 2, -3, 4]));
+        assert_eq!(numbers::<u32>(""), Ok(vec![]));
+        let error = numbers::<u32>("1 -2").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                source_line: "1 -2".to_string(),
+                ..ParseError::new(1, 3, "invalid number \"-2\"")
+            }
+        );
+
+        let line = "Card 1: 41 48 | 83 x";
+        let error = numbers::<u32>(&line[16..])
+            .unwrap_err()
+            .within(line, &line[16..]);
+        assert_eq!((error.line, error.column), (1, 20));
+        assert_eq!(error.source_line, line);
+    }
+
+    #[test]
+    fn test_lines_and_sections() {
+        let input = "seeds: 1 2\n\n\nmap:\n1 2 3\n4 x 6\n";
+        let parts = sections(input);
+        assert_eq!(
+            parts,
+            vec![
+                Section {
+                    text: "seeds: 1 2",
+                    line: 1
+                },
+                Section {
+                    text: "map:\n1 2 3\n4 x 6",
+                    line: 4
+                }
+            ]
+        );
+
+        let error = parts[1]
+            .parse(|text| {
+                lines(text, |line| match line.strip_suffix(':') {
+                    Some(_) => Ok(vec![]),
+                    None => numbers::<i32>(line),
+                })
+            })
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (6, 3));
+    }
+
+    #[test]
+    fn test_mapping() {
+        assert_eq!(
+            mapping("AAA = (BBB, CCC)", " = "),
+            Ok(("AAA", vec!["BBB", "CCC"]))
+        );
+        assert_eq!(
+            mapping("jqt: rhn xhk nvd", ":"),
+            Ok(("jqt", vec!["rhn", "xhk", "nvd"]))
+        );
+        let error = mapping("jqt rhn", ":").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(error.message, "expected \":\"");
+        assert!(mapping("jqt:", ":").is_err());
+        assert!(mapping(": a", ":").is_err());
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let grid = char_grid("#.\n.#\n", |c| {
+            (c == '#').then_some(1).or((c == '.').then_some(0))
+        });
+        assert_eq!(grid.unwrap().cells(), &[1, 0, 0, 1]);
+
+        let error = char_grid("#.\n.x\n", |c| (c != 'x').then_some(c)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.source_line, ".x");
+    }
+
+    #[test]
+    fn test_nom() {
+        let input = "1, 2 3\n4,5\n6 ,7\n";
+        let result = many1(terminated(number_list, newline))(input);
+        // many1 stops before the line it can't parse, leaving it unconsumed
+        let error = finish(input, result).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.source_line, "6 ,7");
+
+        let input = "1, 2 3\n4,5\n";
+        let result = many1(terminated(number_list, newline))(input);
+        assert_eq!(finish(input, result), Ok(vec![vec![1, 2, 3], vec![4, 5]]));
+    }
+
+    #[test]
+    fn test_aoc_parse() {
+        use aoc_parse::{parser, prelude::*};
+
+        let p = parser!(lines(repeat_sep(u32, " ")));
+        let error: ParseError = p.parse("1 2\n3 -4\n").unwrap_err().into();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
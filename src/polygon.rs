@@ -0,0 +1,151 @@
+//! Area and lattice point counts of simple polygons with integer vertices.
+//!
+//! The area comes from the shoelace formula and the number of lattice points inside from Pick's
+//! theorem, `A = i + b/2 - 1`, where `i` is the number of interior points and `b` the number of
+//! points on the boundary. All arithmetic is on integers, so the results are exact.
+//!
+//! ```
+//! # use advent_of_code::geometry::{Direction, Point};
+//! # use advent_of_code::polygon::Polygon;
+//! // A loop of tiles around a 3x3 square, like the trench of day 18
+//! let polygon = Polygon::from_steps(
+//!     Point::ORIGIN,
+//!     [(Direction::Right, 4), (Direction::Down, 4), (Direction::Left, 4), (Direction::Up, 4)],
+//! );
+//! assert_eq!(polygon.interior_points(), 9);
+//! assert_eq!(polygon.boundary_inclusive_points(), 25);
+//! ```
+use num::integer::gcd;
+use num::rational::Ratio;
+
+use crate::geometry::{Direction, Point};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    /// Creates a polygon from its vertices in order. The last vertex connects back to the first,
+    /// so it does not need to be repeated.
+    pub fn from_vertices(vertices: impl IntoIterator<Item = Point>) -> Self {
+        let mut vertices: Vec<Point> = vertices.into_iter().collect();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Self { vertices }
+    }
+
+    /// Creates a polygon by walking from `start`, taking each step of the given length in its
+    /// direction.
+    pub fn from_steps(start: Point, steps: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut current = start;
+        let vertices = std::iter::once(start).chain(steps.into_iter().map(|(direction, len)| {
+            current += direction.offset() * len;
+            current
+        }));
+        Self::from_vertices(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    /// Twice the signed area. This is always an integer, unlike the area itself.
+    ///
+    /// The sign is positive when the vertices run clockwise on a map, where `y` grows downwards,
+    /// and negative when they run counter-clockwise.
+    pub fn twice_signed_area(&self) -> i64 {
+        self.edges().map(|(a, b)| a.x * b.y - b.x * a.y).sum()
+    }
+
+    /// The signed area, see [`Polygon::twice_signed_area`] for the sign.
+    pub fn signed_area(&self) -> Ratio<i64> {
+        Ratio::new(self.twice_signed_area(), 2)
+    }
+
+    /// The length of the boundary, counting each edge by its Manhattan length. For polygons
+    /// with only horizontal and vertical edges this is the exact perimeter.
+    pub fn perimeter(&self) -> i64 {
+        self.edges().map(|(a, b)| a.manhattan(b)).sum()
+    }
+
+    /// The number of lattice points on the boundary, including the vertices.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| gcd((b.x - a.x).abs(), (b.y - a.y).abs()))
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside the polygon, using Pick's theorem.
+    pub fn interior_points(&self) -> i64 {
+        (self.twice_signed_area().abs() - self.boundary_points() + 2) / 2
+    }
+
+    /// The number of lattice points inside or on the boundary of the polygon.
+    pub fn boundary_inclusive_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(width: i64, height: i64) -> Polygon {
+        Polygon::from_steps(
+            Point::new(2, 3),
+            [
+                (Direction::Right, width),
+                (Direction::Down, height),
+                (Direction::Left, width),
+                (Direction::Up, height),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_clockwise() {
+        let polygon = rectangle(4, 2);
+        assert_eq!(polygon.vertices().len(), 4);
+        assert_eq!(polygon.twice_signed_area(), 16);
+        assert_eq!(polygon.signed_area(), Ratio::from_integer(8));
+        assert_eq!(polygon.perimeter(), 12);
+        assert_eq!(polygon.boundary_points(), 12);
+        assert_eq!(polygon.interior_points(), 3);
+        assert_eq!(polygon.boundary_inclusive_points(), 15);
+    }
+
+    #[test]
+    fn test_counter_clockwise() {
+        let clockwise = rectangle(4, 2);
+        let polygon = Polygon::from_vertices(clockwise.vertices().iter().rev().copied());
+        assert_eq!(polygon.twice_signed_area(), -16);
+        assert_eq!(polygon.perimeter(), 12);
+        assert_eq!(polygon.interior_points(), 3);
+        assert_eq!(polygon.boundary_inclusive_points(), 15);
+    }
+
+    #[test]
+    fn test_irregular() {
+        // An L shape and a triangle with a diagonal edge
+        let l_shape = Polygon::from_vertices(
+            [(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4), (0, 0)].map(Point::from),
+        );
+        assert_eq!(l_shape.vertices().len(), 6);
+        assert_eq!(l_shape.signed_area(), Ratio::from_integer(12));
+        assert_eq!(l_shape.boundary_inclusive_points(), 21);
+
+        let triangle = Polygon::from_vertices([(0, 0), (3, 0), (0, 3)].map(Point::from));
+        assert_eq!(triangle.signed_area(), Ratio::new(9, 2));
+        assert_eq!(triangle.boundary_points(), 9);
+        assert_eq!(triangle.interior_points(), 1);
+    }
+}
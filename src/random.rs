@@ -0,0 +1,159 @@
+//! Reproducible randomness for solutions.
+//!
+//! A randomised solution, such as Karger's min cut, takes a different number of tries on every
+//! run, which makes benchmarks noisy and a failing run impossible to repeat. Solutions should
+//! draw from [`rng`] instead of `rand::thread_rng()`: it is seeded once per process, and the
+//! runner prints the seed after the answers and stores it with the timings, so that any run can
+//! be replayed with `cargo solve NN --seed <seed>`.
+//!
+//! The seed comes from, in order:
+//! 1. `--seed <n>` on the command line;
+//! 2. the `AOC_SEED` environment variable, which can be set in `.cargo/config.toml`;
+//! 3. [`BENCH_SEED`] when benchmarking with `--time`, so that `cargo time` measures the same work
+//!    on every run;
+//! 4. a random seed otherwise.
+//!
+//! ```
+//! # use advent_of_code::random;
+//! use rand::Rng;
+//!
+//! let mut rng = random::rng();
+//! let roll = rng.gen_range(1..=6);
+//! assert_eq!(random::rng().gen_range(1..=6), roll);
+//! ```
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::{env, process};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// The seed used while benchmarking, unless another is given.
+pub const BENCH_SEED: u64 = 2023;
+
+/// The generator handed out by [`rng`].
+pub type Rng = StdRng;
+
+/// Where the seed of a run came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Cli,
+    Config,
+    Benchmark,
+    Random,
+}
+
+/// The seed of a run, and where it came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Seed {
+    pub value: u64,
+    pub source: Source,
+}
+
+impl Seed {
+    /// Picks the seed from the value of `--seed`, then of `AOC_SEED`, then the benchmark
+    /// default, and finally a random one. Returns an error for a value that isn't a number.
+    pub fn resolve(
+        cli: Option<&str>,
+        config: Option<&str>,
+        benchmarking: bool,
+    ) -> Result<Self, String> {
+        let parse = |value: &str, name: &str| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid {name} \"{value}\", expected a number"))
+        };
+
+        let (value, source) = match (cli, config) {
+            (Some(cli), _) => (parse(cli, "--seed")?, Source::Cli),
+            (None, Some(config)) => (parse(config, "AOC_SEED")?, Source::Config),
+            (None, None) if benchmarking => (BENCH_SEED, Source::Benchmark),
+            (None, None) => (rand::random(), Source::Random),
+        };
+        Ok(Self { value, source })
+    }
+}
+
+/// Writes the seed with its source, such as `2023 (benchmark default)`.
+impl Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self.source {
+            Source::Cli => "from --seed",
+            Source::Config => "from AOC_SEED",
+            Source::Benchmark => "benchmark default",
+            Source::Random => "random",
+        };
+        write!(f, "{} ({source})", self.value)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+static USED: AtomicBool = AtomicBool::new(false);
+
+/// The seed of this process, picked on first use.
+pub fn seed() -> Seed {
+    static SEED: OnceLock<Seed> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        let cli = args
+            .iter()
+            .position(|a| a == "--seed")
+            .map(|index| args.get(index + 1).cloned().unwrap_or_default());
+        let config = env::var("AOC_SEED").ok().filter(|s| !s.is_empty());
+        let benchmarking = args.iter().any(|a| a == "--time");
+
+        Seed::resolve(cli.as_deref(), config.as_deref(), benchmarking).unwrap_or_else(|e| {
+            eprintln!("Error: {e}. Format: cargo solve 1 --seed <number>");
+            process::exit(1);
+        })
+    })
+}
+
+/// A generator seeded with the seed of this process. Every call starts from the same state, so
+/// each run of a part, including each benchmark iteration, makes the same choices.
+pub fn rng() -> Rng {
+    USED.store(true, Ordering::Relaxed);
+    Rng::seed_from_u64(seed().value)
+}
+
+/// The seed of this process, if a solution has drawn from [`rng`] since the last call. Taking it
+/// clears the use, so that running several days in one process only reports the seed for the
+/// days that drew from [`rng`].
+pub fn take_used_seed() -> Option<Seed> {
+    USED.swap(false, Ordering::Relaxed).then(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let seed = Seed::resolve(Some("7"), Some("8"), true).unwrap();
+        assert_eq!(seed.value, 7);
+        assert_eq!(seed.to_string(), "7 (from --seed)");
+
+        let seed = Seed::resolve(None, Some(" 8 "), true).unwrap();
+        assert_eq!((seed.value, seed.source), (8, Source::Config));
+
+        let seed = Seed::resolve(None, None, true).unwrap();
+        assert_eq!((seed.value, seed.source), (BENCH_SEED, Source::Benchmark));
+        assert_eq!(
+            Seed::resolve(None, None, false).unwrap().source,
+            Source::Random
+        );
+
+        assert!(Seed::resolve(Some("x"), None, false).is_err());
+        assert!(Seed::resolve(None, Some("-1"), false).is_err());
+    }
+
+    #[test]
+    fn test_take_used_seed() {
+        let _ = rng();
+        assert_eq!(take_used_seed(), Some(seed()));
+        assert_eq!(take_used_seed(), None);
+    }
+}
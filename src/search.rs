@@ -0,0 +1,317 @@
+//! Shortest-path searches over implicit graphs.
+//!
+//! Nodes can be any hashable type, such as a position combined with the direction it was
+//! entered from. Edges are produced on demand by a successor closure, so the graph never needs
+//! to be built up front.
+//!
+//! ```
+//! # use advent_of_code::search::Search;
+//! // Find the cheapest way to count from 1 to 10, where adding one costs 1 and doubling costs 2
+//! let found = Search::new(1)
+//!     .with_path()
+//!     .dijkstra(|&n| [(n + 1, 1), (n * 2, 2)], |&n| n == 10)
+//!     .unwrap();
+//! assert_eq!(found.cost, 6);
+//! assert_eq!(found.path(), Some(&[1, 2, 4, 5, 10][..]));
+//! ```
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use num_traits::Zero;
+use priority_queue::DoublePriorityQueue;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The outcome of a successful search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Found<N, C> {
+    /// The goal node that was reached.
+    pub goal: N,
+    /// The total cost of reaching the goal.
+    pub cost: C,
+    path: Option<Vec<N>>,
+}
+
+impl<N, C> Found<N, C> {
+    /// The nodes from the start to the goal, inclusive. Only available if the search was
+    /// created with [`Search::with_path`].
+    pub fn path(&self) -> Option<&[N]> {
+        self.path.as_deref()
+    }
+}
+
+/// A search from a start node, see the [module documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct Search<N> {
+    start: N,
+    track_path: bool,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    pub fn new(start: N) -> Self {
+        Self {
+            start,
+            track_path: false,
+        }
+    }
+
+    /// Remember the predecessor of each node, so that the path to the goal can be rebuilt.
+    /// This is slower and uses more memory, so only enable it when the path is needed.
+    pub fn with_path(mut self) -> Self {
+        self.track_path = true;
+        self
+    }
+
+    /// Breadth-first search, where every step costs 1.
+    pub fn bfs<I>(
+        &self,
+        mut successors: impl FnMut(&N) -> I,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<Found<N, usize>>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let mut queue = VecDeque::from([(self.start.clone(), 0)]);
+        let mut seen = FxHashSet::default();
+        seen.insert(self.start.clone());
+        let mut parents = FxHashMap::default();
+
+        while let Some((current, cost)) = queue.pop_front() {
+            if is_goal(&current) {
+                return Some(self.found(current, cost, &parents));
+            }
+
+            for next in successors(&current) {
+                if seen.insert(next.clone()) {
+                    if self.track_path {
+                        parents.insert(next.clone(), Some(current.clone()));
+                    }
+                    queue.push_back((next, cost + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// Breadth-first search visiting every reachable node, returning the number of steps to
+    /// each of them.
+    pub fn bfs_distances<I>(&self, mut successors: impl FnMut(&N) -> I) -> FxHashMap<N, usize>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let mut queue = VecDeque::from([self.start.clone()]);
+        let mut distances = FxHashMap::default();
+        distances.insert(self.start.clone(), 0);
+
+        while let Some(current) = queue.pop_front() {
+            let cost = distances[&current] + 1;
+            for next in successors(&current) {
+                distances.entry(next.clone()).or_insert_with(|| {
+                    queue.push_back(next);
+                    cost
+                });
+            }
+        }
+        distances
+    }
+
+    /// Dijkstra's algorithm. `successors` returns each neighbouring node with the cost of moving
+    /// to it, which must not be negative.
+    pub fn dijkstra<C, I>(
+        &self,
+        successors: impl FnMut(&N) -> I,
+        is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<Found<N, C>>
+    where
+        C: Copy + Ord + Zero,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        self.astar(successors, |_| C::zero(), is_goal)
+    }
+
+    /// A* search. `heuristic` estimates the remaining cost to the nearest goal and must never
+    /// overestimate it, otherwise the result may not be the cheapest. Manhattan distance is the
+    /// usual choice on a grid.
+    pub fn astar<C, I>(
+        &self,
+        mut successors: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<Found<N, C>>
+    where
+        C: Copy + Ord + Zero,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        // The queue is ordered by the estimated total cost and must be popped from the minimum
+        let mut queue = DoublePriorityQueue::new();
+        queue.push(self.start.clone(), heuristic(&self.start));
+
+        let mut costs = FxHashMap::default();
+        costs.insert(self.start.clone(), C::zero());
+        let mut parents = FxHashMap::default();
+        parents.insert(self.start.clone(), None);
+
+        while let Some((current, _)) = queue.pop_min() {
+            let cost = costs[&current];
+            if is_goal(&current) {
+                return Some(self.found(current, cost, &parents));
+            }
+
+            for (next, step) in successors(&current) {
+                let cost = cost + step;
+                if costs.get(&next).is_none_or(|c| cost < *c) {
+                    costs.insert(next.clone(), cost);
+                    if self.track_path {
+                        parents.insert(next.clone(), Some(current.clone()));
+                    }
+                    let estimate = cost + heuristic(&next);
+                    queue.push(next, estimate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Counts the distinct cheapest paths to the goal nodes, returning the cost and the number of
+    /// paths. If several goal nodes share the cheapest cost, their paths are all counted.
+    ///
+    /// Every step must have a positive cost, otherwise the count is not reliable.
+    pub fn count_shortest_paths<C, I>(
+        &self,
+        mut successors: impl FnMut(&N) -> I,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<(C, usize)>
+    where
+        C: Copy + Ord + Zero,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let mut queue = DoublePriorityQueue::new();
+        queue.push(self.start.clone(), C::zero());
+
+        let mut best = FxHashMap::default();
+        best.insert(self.start.clone(), (C::zero(), 1));
+        let mut result: Option<(C, usize)> = None;
+
+        while let Some((current, cost)) = queue.pop_min() {
+            match result {
+                Some((goal_cost, _)) if cost > goal_cost => break,
+                _ => {}
+            }
+
+            let count = best[&current].1;
+            if is_goal(&current) {
+                let (_, total) = result.get_or_insert((cost, 0));
+                *total += count;
+                continue;
+            }
+
+            for (next, step) in successors(&current) {
+                let cost = cost + step;
+                match best.get_mut(&next) {
+                    Some((c, n)) if cost == *c => *n += count,
+                    Some((c, _)) if cost > *c => {}
+                    _ => {
+                        best.insert(next.clone(), (cost, count));
+                        queue.push(next, cost);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn found<C>(&self, goal: N, cost: C, parents: &FxHashMap<N, Option<N>>) -> Found<N, C> {
+        let path = self.track_path.then(|| {
+            let mut path = vec![goal.clone()];
+            while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            path
+        });
+        Found { goal, cost, path }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(
+            "\
+..#.
+.#..
+....
+#.#.
+",
+            |c| c == '#',
+        )
+    }
+
+    fn open_neighbours(grid: &Grid<bool>, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        grid.neighbours4(x, y).filter(|&p| !grid[p]).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let found = Search::new((0, 0))
+            .with_path()
+            .bfs(|&p| open_neighbours(&grid, p), |&p| p == (3, 0))
+            .unwrap();
+        assert_eq!(found.cost, 7);
+        assert_eq!(found.path().unwrap().len(), 8);
+        assert_eq!(found.path().unwrap()[0], (0, 0));
+
+        let untracked = Search::new((0, 0)).bfs(|&p| open_neighbours(&grid, p), |&p| p == (3, 0));
+        assert_eq!(untracked.unwrap().path(), None);
+
+        let distances = Search::new((0, 0)).bfs_distances(|&p| open_neighbours(&grid, p));
+        assert_eq!(distances[&(3, 3)], 6);
+        assert_eq!(distances.get(&(0, 3)), None);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // Moving right costs 1, moving down costs 3, on a 6x6 board
+        let successors = |&(x, y): &(i32, i32)| {
+            [((x + 1, y), 1), ((x, y + 1), 3)]
+                .into_iter()
+                .filter(|((x, y), _)| *x < 6 && *y < 6)
+        };
+        let is_goal = |p: &(i32, i32)| *p == (4, 2);
+
+        let found = Search::new((0, 0))
+            .with_path()
+            .dijkstra(successors, is_goal)
+            .unwrap();
+        assert_eq!(found.cost, 10);
+        assert_eq!(found.path().unwrap().len(), 7);
+
+        let found = Search::new((0, 0))
+            .astar(successors, |&(x, y)| (4 - x) + (2 - y), is_goal)
+            .unwrap();
+        assert_eq!(found.cost, 10);
+        assert_eq!(found.goal, (4, 2));
+
+        let unreachable = Search::new((0, 0)).dijkstra(successors, |p| *p == (-1, 0));
+        assert_eq!(unreachable, None::<Found<_, i32>>);
+    }
+
+    #[test]
+    fn test_count_shortest_paths() {
+        // All monotonic paths across a 3x2 lattice of unit steps
+        let successors = |&(x, y): &(u32, u32)| {
+            [((x + 1, y), 1), ((x, y + 1), 1)]
+                .into_iter()
+                .filter(|((x, y), _)| *x <= 3 && *y <= 2)
+        };
+        let result = Search::new((0, 0)).count_shortest_paths(successors, |p| *p == (3, 2));
+        assert_eq!(result, Some((5, 10)));
+
+        // Two goals at the same cost both count
+        let result = Search::new((0, 0)).count_shortest_paths(successors, |&(x, y)| x + y == 1);
+        assert_eq!(result, Some((1, 2)));
+    }
+}
@@ -0,0 +1,147 @@
+//! Extrapolating integer sequences that follow a polynomial.
+//!
+//! A sequence whose differences eventually become constant is generated by a polynomial, and a
+//! polynomial of degree `d` is fixed by any `d + 1` of its values. [`Polynomial`] fits one exactly
+//! with Newton's divided differences over rationals, then evaluates it anywhere, including before
+//! the first sample.
+//!
+//! ```
+//! # use advent_of_code::sequence::Polynomial;
+//! // The triangular numbers, n * (n + 1) / 2
+//! let triangular = Polynomial::from_sequence(&[0, 1, 3, 6, 10]);
+//! assert_eq!(triangular.degree(), Some(2));
+//! assert_eq!(triangular.eval_i64(100), Some(5050));
+//! assert_eq!(triangular.eval_i64(-3), Some(3));
+//! ```
+use num::{BigInt, BigRational, ToPrimitive, Zero};
+
+/// A polynomial in Newton form: `c0 + c1 (x - x0) + c2 (x - x0)(x - x1) + ...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial {
+    nodes: Vec<BigRational>,
+    coefficients: Vec<BigRational>,
+}
+
+fn rational(v: i64) -> BigRational {
+    BigRational::from_integer(BigInt::from(v))
+}
+
+impl Polynomial {
+    /// Fits the polynomial of lowest degree through the given `(x, y)` points.
+    ///
+    /// # Panics
+    /// Panics if two points share an `x` value.
+    pub fn interpolate(points: &[(i64, i64)]) -> Self {
+        let nodes: Vec<BigRational> = points.iter().map(|&(x, _)| rational(x)).collect();
+        let mut table: Vec<BigRational> = points.iter().map(|&(_, y)| rational(y)).collect();
+
+        // Each level of the divided difference table has one fewer entry, and its first entry
+        // is the next coefficient
+        let mut coefficients = Vec::with_capacity(points.len());
+        for level in 0..points.len() {
+            if level > 0 {
+                for i in 0..points.len() - level {
+                    let width = &nodes[i + level] - &nodes[i];
+                    assert!(!width.is_zero(), "points must have distinct x values");
+                    table[i] = (&table[i + 1] - &table[i]) / width;
+                }
+            }
+            coefficients.push(table[0].clone());
+        }
+
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+
+        Self {
+            nodes,
+            coefficients,
+        }
+    }
+
+    /// Fits the polynomial of lowest degree through a sequence, taking the first value to be at
+    /// index 0. The coefficients are then the forward differences of the sequence.
+    pub fn from_sequence(values: &[i64]) -> Self {
+        let points: Vec<(i64, i64)> = (0..).zip(values.iter().copied()).collect();
+        Self::interpolate(&points)
+    }
+
+    /// The degree of the polynomial, or `None` for the zero polynomial.
+    ///
+    /// A degree lower than the number of points minus one means the extra points confirmed the
+    /// fit, rather than only determining it.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// The value of the polynomial at `x`.
+    pub fn eval(&self, x: i64) -> BigRational {
+        let x = rational(x);
+        let Some((last, rest)) = self.coefficients.split_last() else {
+            return BigRational::zero();
+        };
+        rest.iter()
+            .zip(&self.nodes)
+            .rev()
+            .fold(last.clone(), |acc, (c, node)| acc * (&x - node) + c)
+    }
+
+    /// The value of the polynomial at `x`, or `None` if it is not an integer or does not fit in
+    /// an `i64`.
+    pub fn eval_i64(&self, x: i64) -> Option<i64> {
+        let value = self.eval(x);
+        value.is_integer().then(|| value.to_integer().to_i64())?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sequence() {
+        // The example sequences of day 9
+        let sequence = Polynomial::from_sequence(&[0, 3, 6, 9, 12, 15]);
+        assert_eq!(sequence.degree(), Some(1));
+        assert_eq!(sequence.eval_i64(6), Some(18));
+        assert_eq!(sequence.eval_i64(-1), Some(-3));
+
+        let sequence = Polynomial::from_sequence(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(sequence.degree(), Some(3));
+        assert_eq!(sequence.eval_i64(6), Some(68));
+        assert_eq!(sequence.eval_i64(-1), Some(5));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let zero = Polynomial::from_sequence(&[0, 0, 0]);
+        assert_eq!(zero.degree(), None);
+        assert_eq!(zero.eval_i64(1000), Some(0));
+        assert_eq!(Polynomial::from_sequence(&[]).eval_i64(5), Some(0));
+
+        let constant = Polynomial::from_sequence(&[7]);
+        assert_eq!(constant.degree(), Some(0));
+        assert_eq!(constant.eval_i64(-50), Some(7));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // y = x^2 / 2 sampled at uneven points, so it is not an integer everywhere
+        let half_square = Polynomial::interpolate(&[(-2, 2), (4, 8), (10, 50)]);
+        assert_eq!(half_square.degree(), Some(2));
+        assert_eq!(half_square.eval_i64(6), Some(18));
+        assert_eq!(half_square.eval(3), BigRational::new(9.into(), 2.into()));
+        assert_eq!(half_square.eval_i64(3), None);
+
+        // Large values are exact
+        let big = Polynomial::interpolate(&[(0, 0), (1, 1), (2, 4)]);
+        assert_eq!(big.eval_i64(3_000_000_000), Some(9_000_000_000_000_000_000));
+        assert_eq!(big.eval_i64(4_000_000_000), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_x() {
+        Polynomial::interpolate(&[(1, 2), (1, 3)]);
+    }
+}
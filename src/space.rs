@@ -0,0 +1,316 @@
+//! Exact geometry in 3D space, using rationals of arbitrary precision.
+//!
+//! Puzzle coordinates are often large enough that products overflow even an `i128`, and
+//! intersections rarely land on integer coordinates. Working with [`BigRational`] avoids both
+//! problems: every intersection is computed exactly, and can be checked for being an integer
+//! afterwards.
+//!
+//! ```
+//! # use advent_of_code::space::{Line, Vector};
+//! # use num::BigRational;
+//! // x = t, y = t and x = 2 - s, y = s cross at (1, 1, 0)
+//! let a = Line::new(Vector::from_ints(0, 0, 0), Vector::from_ints(1, 1, 0));
+//! let b = Line::new(Vector::from_ints(2, 0, 0), Vector::from_ints(-1, 1, 0));
+//! let (t, s) = a.intersect(&b).unwrap();
+//! assert_eq!(a.at(&t), Vector::from_ints(1, 1, 0));
+//! assert_eq!(t, BigRational::from_integer(1.into()));
+//! assert_eq!(b.at(&s), a.at(&t));
+//! ```
+use std::fmt::Display;
+use std::ops::{Add, Neg, Sub};
+
+use num::{BigInt, BigRational, One, ToPrimitive, Zero};
+
+use crate::geometry::Point3;
+
+/// A point or vector in 3D space with exact rational coordinates.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub x: BigRational,
+    pub y: BigRational,
+    pub z: BigRational,
+}
+
+impl Vector {
+    pub fn new(x: BigRational, y: BigRational, z: BigRational) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn from_ints(x: i64, y: i64, z: i64) -> Self {
+        let r = |v: i64| BigRational::from_integer(BigInt::from(v));
+        Self::new(r(x), r(y), r(z))
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero() && self.z.is_zero()
+    }
+
+    pub fn dot(&self, other: &Vector) -> BigRational {
+        &self.x * &other.x + &self.y * &other.y + &self.z * &other.z
+    }
+
+    /// The cross product, perpendicular to both vectors. It is zero if they are parallel.
+    pub fn cross(&self, other: &Vector) -> Vector {
+        Vector::new(
+            &self.y * &other.z - &self.z * &other.y,
+            &self.z * &other.x - &self.x * &other.z,
+            &self.x * &other.y - &self.y * &other.x,
+        )
+    }
+
+    pub fn scale(&self, factor: &BigRational) -> Vector {
+        Vector::new(&self.x * factor, &self.y * factor, &self.z * factor)
+    }
+
+    /// Converts back to integer coordinates, or returns `None` if a coordinate is not an integer
+    /// or does not fit in an `i64`.
+    pub fn to_point3(&self) -> Option<Point3> {
+        let int = |v: &BigRational| v.is_integer().then(|| v.to_integer().to_i64())?;
+        Some(Point3::new(int(&self.x)?, int(&self.y)?, int(&self.z)?))
+    }
+}
+
+impl From<Point3> for Vector {
+    fn from(p: Point3) -> Self {
+        Self::from_ints(p.x, p.y, p.z)
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl Add for &Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: &Vector) -> Vector {
+        Vector::new(&self.x + &rhs.x, &self.y + &rhs.y, &self.z + &rhs.z)
+    }
+}
+
+impl Sub for &Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: &Vector) -> Vector {
+        Vector::new(&self.x - &rhs.x, &self.y - &rhs.y, &self.z - &rhs.z)
+    }
+}
+
+impl Neg for &Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-&self.x, -&self.y, -&self.z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The line through `point` along `direction`, made up of the points `point + t * direction`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub point: Vector,
+    pub direction: Vector,
+}
+
+impl Line {
+    pub fn new(point: Vector, direction: Vector) -> Self {
+        Self { point, direction }
+    }
+
+    /// The point at parameter `t`.
+    pub fn at(&self, t: &BigRational) -> Vector {
+        &self.point + &self.direction.scale(t)
+    }
+
+    /// Finds where two lines cross, as the parameters `(t, s)` with `self.at(t) == other.at(s)`.
+    ///
+    /// Returns `None` if the lines never cross, either because they are parallel (including the
+    /// same line given twice) or because they are skew.
+    pub fn intersect(&self, other: &Line) -> Option<(BigRational, BigRational)> {
+        let normal = self.direction.cross(&other.direction);
+        if normal.is_zero() {
+            return None;
+        }
+        let offset = &other.point - &self.point;
+        if !offset.dot(&normal).is_zero() {
+            return None;
+        }
+
+        let length = normal.dot(&normal);
+        let t = offset.cross(&other.direction).dot(&normal) / &length;
+        let s = offset.cross(&self.direction).dot(&normal) / &length;
+        Some((t, s))
+    }
+
+    /// Finds where the line crosses a plane, as the parameter `t` of the point `self.at(t)`.
+    ///
+    /// Returns `None` if the line is parallel to the plane, whether or not it lies within it.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<BigRational> {
+        let rate = plane.normal.dot(&self.direction);
+        if rate.is_zero() {
+            return None;
+        }
+        Some((&plane.offset - plane.normal.dot(&self.point)) / rate)
+    }
+}
+
+/// The plane of points `p` with `normal · p == offset`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plane {
+    pub normal: Vector,
+    pub offset: BigRational,
+}
+
+impl Plane {
+    /// The plane through `point` that contains the directions `u` and `v`, or `None` if they are
+    /// parallel.
+    pub fn through(point: &Vector, u: &Vector, v: &Vector) -> Option<Self> {
+        let normal = u.cross(v);
+        if normal.is_zero() {
+            return None;
+        }
+        let offset = normal.dot(point);
+        Some(Self { normal, offset })
+    }
+
+    pub fn contains(&self, point: &Vector) -> bool {
+        self.normal.dot(point) == self.offset
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Solves the square linear system `matrix * x == rhs` by Gauss-Jordan elimination.
+///
+/// Returns `None` if the system has no unique solution.
+///
+/// # Panics
+/// Panics if `matrix` is not square with as many rows as `rhs`.
+pub fn solve_linear(
+    mut matrix: Vec<Vec<BigRational>>,
+    mut rhs: Vec<BigRational>,
+) -> Option<Vec<BigRational>> {
+    let n = rhs.len();
+    assert!(
+        matrix.len() == n && matrix.iter().all(|row| row.len() == n),
+        "expected a {n}x{n} matrix"
+    );
+
+    for column in 0..n {
+        let pivot = (column..n).find(|&row| !matrix[row][column].is_zero())?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        // Scale the pivot row to a leading one, then clear the column from every other row
+        let inverse = BigRational::one() / &matrix[column][column];
+        for value in &mut matrix[column] {
+            *value *= &inverse;
+        }
+        rhs[column] *= &inverse;
+
+        let (pivot_row, pivot_rhs) = (matrix[column].clone(), rhs[column].clone());
+        for (row, (values, value_rhs)) in matrix.iter_mut().zip(&mut rhs).enumerate() {
+            if row == column || values[column].is_zero() {
+                continue;
+            }
+            let factor = values[column].clone();
+            for (value, pivot) in values.iter_mut().zip(&pivot_row).skip(column) {
+                *value -= &factor * pivot;
+            }
+            *value_rhs -= &factor * &pivot_rhs;
+        }
+    }
+
+    Some(rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i64, d: i64) -> BigRational {
+        BigRational::new(n.into(), d.into())
+    }
+
+    #[test]
+    fn test_vector_products() {
+        let a = Vector::from_ints(1, 2, 3);
+        let b = Vector::from_ints(4, 5, 6);
+        assert_eq!(a.dot(&b), r(32, 1));
+        assert_eq!(a.cross(&b), Vector::from_ints(-3, 6, -3));
+        assert!(a.cross(&a.scale(&r(-5, 2))).is_zero());
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_eq!((-&a).to_point3(), Some(Point3::new(-1, -2, -3)));
+        assert_eq!(a.scale(&r(1, 2)).to_point3(), None);
+    }
+
+    #[test]
+    fn test_large_values() {
+        // Products of coordinates this large overflow an i128
+        let big = Vector::from_ints(i64::MAX, i64::MAX, 1);
+        let squared = big.dot(&big);
+        assert_eq!(
+            squared.to_integer(),
+            BigInt::from(i64::MAX) * BigInt::from(i64::MAX) * 2 + 1
+        );
+    }
+
+    #[test]
+    fn test_line_intersection() {
+        // Hailstones from the example of day 24, projected onto the xy plane
+        let a = Line::new(Vector::from_ints(19, 13, 0), Vector::from_ints(-2, 1, 0));
+        let b = Line::new(Vector::from_ints(18, 19, 0), Vector::from_ints(-1, -1, 0));
+        let (t, s) = a.intersect(&b).unwrap();
+        assert_eq!(a.at(&t), Vector::new(r(43, 3), r(46, 3), r(0, 1)));
+        assert_eq!(a.at(&t), b.at(&s));
+
+        // Parallel
+        let c = Line::new(Vector::from_ints(20, 25, 0), Vector::from_ints(-2, -2, 0));
+        assert_eq!(b.intersect(&c), None);
+        // Skew
+        let d = Line::new(Vector::from_ints(0, 0, 1), Vector::from_ints(0, 1, 0));
+        assert_eq!(a.intersect(&d), None);
+    }
+
+    #[test]
+    fn test_plane_intersection() {
+        let plane = Plane::through(
+            &Vector::from_ints(0, 0, 2),
+            &Vector::from_ints(1, 0, 0),
+            &Vector::from_ints(0, 1, 0),
+        )
+        .unwrap();
+        assert!(plane.contains(&Vector::from_ints(5, -3, 2)));
+
+        let line = Line::new(Vector::from_ints(1, 1, 0), Vector::from_ints(1, 0, 3));
+        let t = line.intersect_plane(&plane).unwrap();
+        assert_eq!(t, r(2, 3));
+        assert!(plane.contains(&line.at(&t)));
+
+        let flat = Line::new(Vector::zero(), Vector::from_ints(1, 1, 0));
+        assert_eq!(flat.intersect_plane(&plane), None);
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let matrix = [[1, 1, 1], [0, 2, 5], [2, 5, -1]]
+            .map(|row| row.map(|v| r(v, 1)).to_vec())
+            .to_vec();
+        let rhs = [6, -4, 27].map(|v| r(v, 1)).to_vec();
+        assert_eq!(
+            solve_linear(matrix, rhs),
+            Some(vec![r(5, 1), r(3, 1), r(-2, 1)])
+        );
+
+        let singular = vec![vec![r(1, 1), r(2, 1)], vec![r(2, 1), r(4, 1)]];
+        assert_eq!(solve_linear(singular, vec![r(1, 1), r(2, 1)]), None);
+    }
+}
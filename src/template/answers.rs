@@ -0,0 +1,306 @@
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the outcome of submitting an answer to the advent of code website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Submission {
+    /// The answer has not been submitted, or the submission was not evaluated.
+    Pending,
+    Correct,
+    Incorrect,
+}
+
+impl Submission {
+    /// Determine the submission outcome from the output of `aoc submit`.
+    pub fn from_aoc_output(output: &str) -> Self {
+        if output.contains("That's the right answer")
+            || output.contains("Did you already complete it")
+        {
+            Submission::Correct
+        } else if output.contains("That's not the right answer") {
+            Submission::Incorrect
+        } else {
+            // e.g. "You gave an answer too recently", the answer was not evaluated.
+            Submission::Pending
+        }
+    }
+}
+
+/// Represents the last answer produced for one part of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub value: String,
+    pub submission: Submission,
+}
+
+/// Represents the stored answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Answers::default(),
+            Err(e) => {
+                eprintln!("{e}");
+                return Answers::default();
+            }
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day && a.part == part)
+    }
+
+    /// Record the answer for a part. A changed answer resets its submission state.
+    /// Returns `true` if the stored answers were modified.
+    pub fn record(&mut self, day: Day, part: u8, value: &str) -> bool {
+        match self
+            .data
+            .iter_mut()
+            .find(|a| a.day == day && a.part == part)
+        {
+            Some(answer) if answer.value == value => false,
+            Some(answer) => {
+                answer.value = value.into();
+                answer.submission = Submission::Pending;
+                true
+            }
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part,
+                    value: value.into(),
+                    submission: Submission::Pending,
+                });
+                self.data.sort_unstable_by_key(|a| (a.day, a.part));
+                true
+            }
+        }
+    }
+
+    /// Record the outcome of submitting the answer for a part.
+    /// A pending outcome never overwrites a previous verdict.
+    pub fn record_submission(&mut self, day: Day, part: u8, value: &str, submission: Submission) {
+        self.record(day, part, value);
+        if submission == Submission::Pending {
+            return;
+        }
+        if let Some(answer) = self
+            .data
+            .iter_mut()
+            .find(|a| a.day == day && a.part == part)
+        {
+            answer.submission = submission;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("value".into(), JsonValue::String(value.value.clone()));
+        map.insert(
+            "submission".into(),
+            JsonValue::String(
+                match value.submission {
+                    Submission::Pending => "pending",
+                    Submission::Correct => "correct",
+                    Submission::Incorrect => "incorrect",
+                }
+                .into(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| *part == 1 || *part == 2)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let value = json
+            .get("value")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.value to be a string.")?;
+
+        let submission = match json
+            .get("submission")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("pending") => Submission::Pending,
+            Some("correct") => Submission::Correct,
+            Some("incorrect") => Submission::Incorrect,
+            _ => return Err("Expected answer.submission to be a submission state.".into()),
+        };
+
+        Ok(Answer {
+            day,
+            part,
+            value: value.clone(),
+            submission,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use crate::day;
+
+    use super::{Answers, Submission};
+
+    #[test]
+    fn handles_json_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 2, "value": "281", "submission": "correct" }] }"#
+                .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        let answer = answers.get(day!(1), 2).unwrap();
+        assert_eq!(answer.value, "281");
+        assert_eq!(answer.submission, Submission::Correct);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 3, "value": "1", "submission": "correct" }] }"#
+                .to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn resets_submission_when_answer_changes() {
+        let mut answers = Answers::default();
+        assert!(answers.record(day!(1), 1, "142"));
+        answers.record_submission(day!(1), 1, "142", Submission::Incorrect);
+        assert!(!answers.record(day!(1), 1, "142"));
+        assert_eq!(
+            answers.get(day!(1), 1).unwrap().submission,
+            Submission::Incorrect
+        );
+        assert!(answers.record(day!(1), 1, "143"));
+        assert_eq!(
+            answers.get(day!(1), 1).unwrap().submission,
+            Submission::Pending
+        );
+    }
+
+    #[test]
+    fn keeps_verdict_for_pending_submission() {
+        let mut answers = Answers::default();
+        answers.record_submission(day!(3), 2, "7", Submission::Correct);
+        answers.record_submission(day!(3), 2, "7", Submission::Pending);
+        assert_eq!(
+            answers.get(day!(3), 2).unwrap().submission,
+            Submission::Correct
+        );
+    }
+
+    #[test]
+    fn parses_aoc_output() {
+        assert_eq!(
+            Submission::from_aoc_output("That's the right answer! You are one gold star closer."),
+            Submission::Correct
+        );
+        assert_eq!(
+            Submission::from_aoc_output("That's not the right answer; your answer is too low."),
+            Submission::Incorrect
+        );
+        assert_eq!(
+            Submission::from_aoc_output("You gave an answer too recently."),
+            Submission::Pending
+        );
+    }
+}
@@ -77,7 +77,15 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // capture stdout so the runner can determine whether the answer was accepted.
+    let output = call_aoc_cli_with_stdout(&args, Stdio::piped());
+    let stdout = match &output {
+        Ok(output) | Err(AocCommandError::BadExitStatus(output)) => &output.stdout,
+        Err(_) => return output,
+    };
+    print!("{}", String::from_utf8_lossy(stdout));
+    output
 }
 
 fn get_input_path(day: Day) -> String {
@@ -109,10 +117,14 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with_stdout(args, Stdio::inherit())
+}
+
+fn call_aoc_cli_with_stdout(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
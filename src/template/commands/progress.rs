@@ -0,0 +1,59 @@
+use crate::template::progress::{PartStatus, Progress};
+use crate::template::{readme_progress, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub fn handle(store: bool) {
+    let progress = Progress::collect();
+
+    println!(
+        "{ANSI_BOLD}🎄 Progress:{ANSI_RESET} {} / 50 {}",
+        progress.stars(),
+        PartStatus::Star.symbol()
+    );
+    println!();
+
+    // print the days as an advent calendar, one week of five days per row.
+    for week in progress.data.chunks(5) {
+        let row = week
+            .iter()
+            .map(|day| {
+                let cell = format!("{} {}{}", day.day, day.part_1.symbol(), day.part_2.symbol());
+                if day.stars() == 2 {
+                    format!("{ANSI_BOLD}{cell}{ANSI_RESET}")
+                } else if !day.is_scaffolded() {
+                    format!("{ANSI_ITALIC}{cell}{ANSI_RESET}")
+                } else {
+                    cell
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("   ");
+        println!("  {row}");
+    }
+
+    println!();
+    println!(
+        "{}",
+        [
+            PartStatus::Star,
+            PartStatus::Solved,
+            PartStatus::Rejected,
+            PartStatus::Unsolved,
+        ]
+        .iter()
+        .map(|s| format!("{} {}", s.symbol(), s.description()))
+        .collect::<Vec<_>>()
+        .join("  ")
+    );
+
+    if store {
+        println!();
+        match readme_progress::update(&progress) {
+            Ok(()) => {
+                println!("Stored updated progress.");
+            }
+            Err(e) => {
+                eprintln!("Failed to store updated progress: {e}");
+            }
+        }
+    }
+}
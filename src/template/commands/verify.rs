@@ -0,0 +1,44 @@
+use std::process;
+
+use crate::template::run_multi::child_commands;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(day: Option<Day>) {
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    let mut failed: Vec<Day> = vec![];
+    let mut need_space = false;
+
+    for day in days {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match child_commands::run_solution_with_args(day, true, &["--verify"]) {
+            Ok((output, _)) if output.is_empty() => println!("Not solved."),
+            Ok((_, true)) => {}
+            Ok((_, false)) => failed.push(day),
+            Err(e) => {
+                eprintln!("Failed to run solution: {e}");
+                failed.push(day);
+            }
+        }
+    }
+
+    println!();
+    if failed.is_empty() {
+        println!("{ANSI_BOLD}All inputs verified.{ANSI_RESET}");
+    } else {
+        let days = failed
+            .iter()
+            .map(Day::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!("{ANSI_BOLD}Verification failed for day(s):{ANSI_RESET} {days}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,107 @@
+/// Access to the files of the `data` directory.
+///
+/// With the `embed-inputs` feature, inputs and examples are compiled into the binaries by the
+/// build script, so solutions no longer depend on the working directory and can be copied to
+/// another machine. Each solution only embeds the files of its own day, and registers them when
+/// it runs, so the `all` binary has the files of every day.
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    use std::sync::Mutex;
+
+    /// Pairs of a path relative to the data directory and the contents of the file.
+    pub type Files = &'static [(&'static str, &'static str)];
+
+    static REGISTERED: Mutex<Vec<Files>> = Mutex::new(vec![]);
+
+    pub fn register(files: Files) {
+        let mut registered = REGISTERED.lock().unwrap();
+        if !registered.iter().any(|f| std::ptr::eq(*f, files)) {
+            registered.push(files);
+        }
+    }
+
+    /// The files of every day that has been registered, or `None` if no solution has run, as in
+    /// the tests of a day.
+    pub fn files() -> Option<Vec<(&'static str, &'static str)>> {
+        let registered = REGISTERED.lock().unwrap();
+        (!registered.is_empty())
+            .then(|| registered.iter().flat_map(|f| f.iter().copied()).collect())
+    }
+}
+
+/// Makes the embedded files of a day available to read. Called by the
+/// [`solution!`](crate::solution) macro.
+#[cfg(feature = "embed-inputs")]
+pub fn register_embedded(files: embedded::Files) {
+    embedded::register(files);
+}
+
+/// Resolve a path relative to the data directory.
+pub fn resolve(relative: &Path) -> PathBuf {
+    #[cfg(feature = "embed-inputs")]
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    #[cfg(not(feature = "embed-inputs"))]
+    let root = std::env::current_dir().unwrap();
+
+    root.join("data").join(relative)
+}
+
+/// Read a file relative to the data directory.
+#[cfg(not(feature = "embed-inputs"))]
+pub fn read_to_string(relative: &Path) -> io::Result<String> {
+    std::fs::read_to_string(resolve(relative))
+}
+
+/// Read a file relative to the data directory from the files embedded at compile time. Tests
+/// don't run a solution to register its files, so they read the data directory instead.
+#[cfg(feature = "embed-inputs")]
+pub fn read_to_string(relative: &Path) -> io::Result<String> {
+    let Some(files) = embedded::files() else {
+        return std::fs::read_to_string(resolve(relative));
+    };
+    files
+        .iter()
+        .find(|(path, _)| Path::new(path) == relative)
+        .map(|(_, contents)| (*contents).to_string())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "file was not embedded at compile time",
+            )
+        })
+}
+
+/// List the files with the given extension in a directory relative to the data directory, sorted by name.
+#[cfg(not(feature = "embed-inputs"))]
+pub fn list_files(relative_dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(resolve(relative_dir)) else {
+        return vec![];
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == extension))
+        .filter_map(|p| p.file_name().map(|name| relative_dir.join(name)))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// List the embedded files with the given extension in a directory relative to the data directory, sorted by name.
+#[cfg(feature = "embed-inputs")]
+pub fn list_files(relative_dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let Some(files) = embedded::files() else {
+        return vec![];
+    };
+    let mut paths: Vec<PathBuf> = files
+        .iter()
+        .map(|(path, _)| PathBuf::from(path))
+        .filter(|p| p.parent() == Some(relative_dir))
+        .filter(|p| p.extension().is_some_and(|ext| ext == extension))
+        .collect();
+    paths.sort();
+    paths
+}
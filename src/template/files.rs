@@ -0,0 +1,234 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::template::{data, Day};
+
+/// Controls how the end of an input file is treated by [`Normalize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingNewline {
+    /// Leave the end of the file untouched.
+    Keep,
+    /// End the file with exactly one newline, like the puzzle inputs do. Empty files stay empty.
+    Single,
+    /// Remove all trailing newlines.
+    Strip,
+}
+
+/// Options for normalizing the contents of an input or example file.
+///
+/// The default converts `\r\n` line endings and ensures a single trailing newline, which covers
+/// files that were edited on Windows or pasted by hand.
+///
+/// ```
+/// # use advent_of_code::template::{Normalize, TrailingNewline};
+/// let normalize = Normalize {
+///     trailing_whitespace: true,
+///     ..Normalize::default()
+/// };
+/// assert_eq!(normalize.apply("a  \r\nb\n\n"), "a\nb\n");
+/// assert_eq!(Normalize::NONE.apply("a\r\n"), "a\r\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalize {
+    /// Convert `\r\n` line endings to `\n`.
+    pub line_endings: bool,
+    /// Remove spaces and tabs at the end of every line.
+    pub trailing_whitespace: bool,
+    pub trailing_newline: TrailingNewline,
+}
+
+impl Normalize {
+    /// Leaves the file contents untouched.
+    pub const NONE: Self = Self {
+        line_endings: false,
+        trailing_whitespace: false,
+        trailing_newline: TrailingNewline::Keep,
+    };
+
+    /// Applies the normalization options to `s`.
+    #[must_use]
+    pub fn apply(&self, s: &str) -> String {
+        let mut result = if self.line_endings {
+            s.replace("\r\n", "\n")
+        } else {
+            s.to_string()
+        };
+
+        if self.trailing_whitespace {
+            result = result
+                .split('\n')
+                .map(|line| line.trim_end_matches([' ', '\t']))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+
+        match self.trailing_newline {
+            TrailingNewline::Keep => {}
+            TrailingNewline::Single => {
+                result.truncate(result.trim_end_matches('\n').len());
+                if !result.is_empty() {
+                    result.push('\n');
+                }
+            }
+            TrailingNewline::Strip => {
+                result.truncate(result.trim_end_matches('\n').len());
+            }
+        }
+
+        result
+    }
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self {
+            line_endings: true,
+            trailing_whitespace: false,
+            trailing_newline: TrailingNewline::Single,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when reading an input or example file.
+/// Its message names the resolved path and how to create the missing file.
+#[derive(Debug)]
+pub struct ReadFileError {
+    pub path: PathBuf,
+    pub source: io::Error,
+    hint: String,
+}
+
+impl ReadFileError {
+    fn new(path: PathBuf, source: io::Error, folder: &str, day: Day) -> Self {
+        let is_scaffolded = Path::new(&format!("src/bin/{day}.rs")).exists();
+        let hint = match (folder, is_scaffolded) {
+            _ if cfg!(feature = "embed-inputs") => {
+                "Add the file and rebuild to embed it into the binary.".into()
+            }
+            ("inputs", false) => format!("Try running `cargo scaffold {day} --download`."),
+            ("inputs", true) => format!("Try running `cargo download {day}`."),
+            (_, false) => format!("Try running `cargo scaffold {day}`."),
+            (_, true) => {
+                "Create the file and paste the example from the puzzle description.".into()
+            }
+        };
+        Self { path, source, hint }
+    }
+}
+
+impl Error for ReadFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not open input file \"{}\": {}",
+            self.path.display(),
+            self.source
+        )?;
+        if self.source.kind() == io::ErrorKind::NotFound {
+            write!(f, ". {}", self.hint)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Helper function that reads a text file to a string, applying the default [`Normalize`] options.
+///
+/// # Panics
+/// Panics with a description of the missing file, see [`try_read_file`] for a non-panicking version.
+#[must_use]
+pub fn read_file(folder: &str, day: Day) -> String {
+    try_read_file(folder, day, Normalize::default()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// Panics with a description of the missing file, see [`try_read_file_part`] for a non-panicking version.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    try_read_file_part(folder, day, part, Normalize::default()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a text file to a string, normalizing its contents.
+pub fn try_read_file(
+    folder: &str,
+    day: Day,
+    normalize: Normalize,
+) -> Result<String, ReadFileError> {
+    read_normalized(folder, day, &format!("{day}.txt"), normalize)
+}
+
+/// Reads a text file with a part suffix to a string, normalizing its contents.
+pub fn try_read_file_part(
+    folder: &str,
+    day: Day,
+    part: u8,
+    normalize: Normalize,
+) -> Result<String, ReadFileError> {
+    read_normalized(folder, day, &format!("{day}-{part}.txt"), normalize)
+}
+
+fn read_normalized(
+    folder: &str,
+    day: Day,
+    file_name: &str,
+    normalize: Normalize,
+) -> Result<String, ReadFileError> {
+    let relative = Path::new(folder).join(file_name);
+    match data::read_to_string(&relative) {
+        Ok(s) => Ok(normalize.apply(&s)),
+        Err(e) => Err(ReadFileError::new(data::resolve(&relative), e, folder, day)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Normalize, TrailingNewline};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(Normalize::default().apply("a\r\nb\r\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn normalizes_trailing_newline() {
+        let normalize = Normalize::default();
+        assert_eq!(normalize.apply("a\nb"), "a\nb\n");
+        assert_eq!(normalize.apply("a\nb\n\n\n"), "a\nb\n");
+        assert_eq!(normalize.apply(""), "");
+
+        let strip = Normalize {
+            trailing_newline: TrailingNewline::Strip,
+            ..Normalize::default()
+        };
+        assert_eq!(strip.apply("a\nb\n\n"), "a\nb");
+    }
+
+    #[test]
+    fn normalizes_trailing_whitespace() {
+        let normalize = Normalize {
+            trailing_whitespace: true,
+            ..Normalize::NONE
+        };
+        assert_eq!(normalize.apply("a \t\n b  \n"), "a\n b\n");
+    }
+
+    #[test]
+    fn keeps_contents_without_normalization() {
+        assert_eq!(Normalize::NONE.apply("a \r\nb\n\n"), "a \r\nb\n\n");
+    }
+}
@@ -0,0 +1,124 @@
+/// Module that discovers the puzzle inputs of a day.
+///
+/// Besides the default `data/inputs/NN.txt`, a day can have a directory `data/inputs/NN/` holding
+/// further inputs, e.g. from other accounts. Each `<name>.txt` in that directory may be accompanied
+/// by a `<name>.answers` file containing the known answer for part one on the first line and for
+/// part two on the second line.
+use std::{io, path::Path};
+
+use crate::template::answers::{Answers, Submission};
+use crate::template::{data, try_read_file, Day, Normalize, ReadFileError};
+
+/// A single puzzle input with the answers it is known to produce.
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub name: String,
+    pub data: String,
+    pub expected: [Option<String>; 2],
+    /// Whether this is the input of the account that answers are submitted for.
+    pub is_primary: bool,
+}
+
+impl Input {
+    /// The known answer for a part, if any.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.expected
+            .get(usize::from(part) - 1)
+            .and_then(|e| e.as_deref())
+    }
+}
+
+/// Read every input of a day, starting with the primary input.
+///
+/// The primary input is `data/inputs/NN.txt` if it exists, or the first input of the
+/// `data/inputs/NN/` directory otherwise. Its expected answers are the ones confirmed by a
+/// submission in `data/answers.json`.
+///
+/// Returns an error naming `data/inputs/NN.txt` if the day has no inputs at all.
+pub fn read_inputs(day: Day) -> Result<Vec<Input>, ReadFileError> {
+    let mut inputs = vec![];
+
+    let missing_primary = match try_read_file("inputs", day, Normalize::default()) {
+        Ok(data) => {
+            let answers = Answers::read_from_file();
+            let confirmed = |part| {
+                answers
+                    .get(day, part)
+                    .filter(|a| a.submission == Submission::Correct)
+                    .map(|a| a.value.clone())
+            };
+
+            inputs.push(Input {
+                name: day.to_string(),
+                data,
+                expected: [confirmed(1), confirmed(2)],
+                is_primary: true,
+            });
+            None
+        }
+        Err(e) if e.source.kind() == io::ErrorKind::NotFound => Some(e),
+        Err(e) => return Err(e),
+    };
+
+    for path in data::list_files(&Path::new("inputs").join(day.to_string()), "txt") {
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Ok(contents) = data::read_to_string(&path) else {
+            eprintln!(
+                "could not open input file {}",
+                data::resolve(&path).display()
+            );
+            continue;
+        };
+
+        inputs.push(Input {
+            name,
+            data: Normalize::default().apply(&contents),
+            expected: read_expected(&path.with_extension("answers")),
+            is_primary: inputs.is_empty(),
+        });
+    }
+
+    match missing_primary {
+        // no inputs were found, report the missing default input.
+        Some(e) if inputs.is_empty() => Err(e),
+        _ => Ok(inputs),
+    }
+}
+
+fn read_expected(path: &Path) -> [Option<String>; 2] {
+    let Ok(s) = data::read_to_string(path) else {
+        return [None, None];
+    };
+
+    let mut lines = s.lines().map(|l| {
+        let l = l.trim();
+        (!l.is_empty()).then(|| l.to_string())
+    });
+
+    [lines.next().flatten(), lines.next().flatten()]
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::read_expected;
+    use std::{env, fs};
+
+    #[test]
+    fn reads_expected_answers() {
+        let path = env::temp_dir().join("advent_of_code_expected.answers");
+        fs::write(&path, "142\n\n").unwrap();
+        assert_eq!(read_expected(&path), [Some("142".to_string()), None]);
+        fs::write(&path, "\n281\n").unwrap();
+        assert_eq!(read_expected(&path), [None, Some("281".to_string())]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let path = env::temp_dir().join("advent_of_code_missing.answers");
+        assert_eq!(read_expected(&path), [None, None]);
+    }
+}
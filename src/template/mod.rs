@@ -1,13 +1,20 @@
-use std::{env, fs};
-
 pub mod aoc_cli;
 pub mod commands;
+pub mod inputs;
 pub mod runner;
 
+#[cfg(feature = "embed-inputs")]
+pub use data::register_embedded;
 pub use day::*;
+pub use files::*;
 
+mod answers;
+mod data;
 mod day;
+mod files;
+mod progress;
 mod readme_benchmarks;
+mod readme_progress;
 mod run_multi;
 mod timings;
 
@@ -15,54 +22,76 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `DAY` and sets up the inputs and runner for each part.
+/// Every input of the day is run, see [`inputs::read_inputs`]; the process exits with a non-zero
+/// status if a part panics or disagrees with a known answer.
+///
+/// Each part returns either `Option<T>`, or [`error::Result<T>`](crate::error::Result) so that a
+/// failure such as a parse error is shown with the day and the offending line of input instead of
+/// panicking.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, `graph = <function>` names a function from the input to a [`Dot`](crate::dot::Dot),
+/// or to a `Result` of one, that `cargo solve <day> --graph` writes out instead of solving.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2]);
+    };
+    ($day:expr, graph = $graph:expr) => {
+        $crate::solution!(@impl $day, [$graph], [part_one, 1] [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@export) => { None };
+    (@export $graph:expr) => {
+        Some(|input: &str| $crate::template::runner::GraphOutput::into_graph($graph(input)))
+    };
+
+    (@impl $day:expr, [$($graph:expr)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The inputs and examples of the day, embedded at compile time.
+        #[cfg(feature = "embed-inputs")]
+        static EMBEDDED_FILES: &[(&str, &str)] =
+            ::std::include!(::std::concat!(::std::env!("OUT_DIR"), "/embedded_", $day, ".rs"));
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
+        /// Runs the solution against every input of the day.
+        /// Returns `false` if a part panicked or disagreed with a known answer.
+        pub fn run() -> bool {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            #[cfg(feature = "embed-inputs")]
+            $crate::template::register_embedded(EMBEDDED_FILES);
+            let Some(inputs) = inputs_to_run(DAY) else {
+                return false;
+            };
+            if graph_requested() {
+                let export: Option<GraphExport> = $crate::solution!(@export $($graph)?);
+                return write_graphs(export, &inputs, DAY);
+            }
+            let mut passed = true;
+            for input in &inputs {
+                print_input_header(input, inputs.len());
+                $( passed &= run_input_part($func, input, DAY, $part); )*
+            }
+            print_seed();
+            passed
+        }
+
+        fn main() {
+            if !run() {
+                std::process::exit(1);
+            }
         }
     };
 }
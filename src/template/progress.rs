@@ -0,0 +1,177 @@
+/// Module that combines scaffolded solutions, stored answers and benchmarks into per-day progress.
+use std::path::Path;
+
+use crate::template::answers::{Answers, Submission};
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day};
+
+use super::run_multi::get_path_for_bin;
+
+/// Represents the progress of a single puzzle part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The day has no solution binary yet.
+    NotScaffolded,
+    /// The solution exists but has not produced an answer yet.
+    Unsolved,
+    /// An answer was produced but has not been confirmed by a submission.
+    Solved,
+    /// The last submitted answer was rejected.
+    Rejected,
+    /// The answer was accepted, i.e. a star was earned.
+    Star,
+}
+
+impl PartStatus {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            PartStatus::NotScaffolded => " ",
+            PartStatus::Unsolved => "·",
+            PartStatus::Solved => "☆",
+            PartStatus::Rejected => "✖",
+            PartStatus::Star => "★",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            PartStatus::NotScaffolded => "not scaffolded",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Solved => "answered, not submitted",
+            PartStatus::Rejected => "rejected",
+            PartStatus::Star => "star",
+        }
+    }
+}
+
+/// Represents the progress of a single day.
+#[derive(Clone, Debug)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+}
+
+impl DayProgress {
+    pub fn is_scaffolded(&self) -> bool {
+        self.part_1 != PartStatus::NotScaffolded
+    }
+
+    pub fn stars(&self) -> usize {
+        [self.part_1, self.part_2]
+            .iter()
+            .filter(|p| **p == PartStatus::Star)
+            .count()
+    }
+}
+
+/// Represents the progress of every day of advent.
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub data: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Collect progress from the solution binaries, `data/answers.json` and `data/timings.json`.
+    pub fn collect() -> Self {
+        let answers = Answers::read_from_file();
+        let timings = Timings::read_from_file();
+        Self::from_sources(
+            |day| Path::new(&get_path_for_bin(day)).exists(),
+            &answers,
+            &timings,
+        )
+    }
+
+    pub fn from_sources(
+        is_scaffolded: impl Fn(Day) -> bool,
+        answers: &Answers,
+        timings: &Timings,
+    ) -> Self {
+        let part_status = |day: Day, part: u8| {
+            if !is_scaffolded(day) {
+                return PartStatus::NotScaffolded;
+            }
+
+            match answers.get(day, part).map(|a| a.submission) {
+                Some(Submission::Correct) => PartStatus::Star,
+                Some(Submission::Incorrect) => PartStatus::Rejected,
+                Some(Submission::Pending) => PartStatus::Solved,
+                None => {
+                    // a benchmark is only recorded for parts that returned an answer.
+                    let benched = timings.data.iter().any(|t| {
+                        t.day == day
+                            && match part {
+                                1 => t.part_1.is_some(),
+                                _ => t.part_2.is_some(),
+                            }
+                    });
+                    if benched {
+                        PartStatus::Solved
+                    } else {
+                        PartStatus::Unsolved
+                    }
+                }
+            }
+        };
+
+        Progress {
+            data: all_days()
+                .map(|day| DayProgress {
+                    day,
+                    part_1: part_status(day, 1),
+                    part_2: part_status(day, 2),
+                })
+                .collect(),
+        }
+    }
+
+    /// Count of accepted answers.
+    pub fn stars(&self) -> usize {
+        self.data.iter().map(DayProgress::stars).sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use crate::{
+        day,
+        template::{
+            answers::{Answers, Submission},
+            timings::{Timing, Timings},
+        },
+    };
+
+    use super::{PartStatus, Progress};
+
+    #[test]
+    fn combines_sources() {
+        let mut answers = Answers::default();
+        answers.record_submission(day!(1), 1, "142", Submission::Correct);
+        answers.record_submission(day!(1), 2, "280", Submission::Incorrect);
+        answers.record(day!(2), 1, "8");
+
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(3),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                total_nanos: 1_000_000_f64,
+                seed: None,
+            }],
+        };
+
+        let progress = Progress::from_sources(|day| day <= 4, &answers, &timings);
+
+        assert_eq!(progress.data[0].part_1, PartStatus::Star);
+        assert_eq!(progress.data[0].part_2, PartStatus::Rejected);
+        assert_eq!(progress.data[1].part_1, PartStatus::Solved);
+        assert_eq!(progress.data[1].part_2, PartStatus::Unsolved);
+        assert_eq!(progress.data[2].part_1, PartStatus::Solved);
+        assert_eq!(progress.data[2].part_2, PartStatus::Unsolved);
+        assert_eq!(progress.data[4].part_1, PartStatus::NotScaffolded);
+        assert_eq!(progress.stars(), 1);
+    }
+}
@@ -0,0 +1,115 @@
+/// Module that updates the readme with star counts and the status of each day.
+/// Works the same way as the benchmarking table, using a separate pair of markers.
+use std::fs;
+
+use crate::template::progress::Progress;
+use crate::template::readme_benchmarks::{get_path_for_bin, locate_table, Error};
+
+static MARKER: &str = "<!--- progress table --->";
+
+fn construct_table(prefix: &str, progress: &Progress) -> String {
+    let header = format!("{prefix} Progress");
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        format!("**⭐ {} / 50**", progress.stars()),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---:  |".into(),
+    ];
+
+    for day in progress.data.iter().filter(|d| d.is_scaffolded()) {
+        let path = get_path_for_bin(day.day);
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} |",
+            day.day.into_inner(),
+            path,
+            day.part_1.symbol(),
+            day.part_2.symbol()
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("★ star, ☆ answered but not submitted, ✖ rejected, · unsolved".into());
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(progress: &Progress) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::{
+        day,
+        template::{
+            answers::{Answers, Submission},
+            progress::Progress,
+            timings::Timings,
+        },
+    };
+
+    fn get_mock_progress() -> Progress {
+        let mut answers = Answers::default();
+        answers.record_submission(day!(1), 1, "142", Submission::Correct);
+        answers.record_submission(day!(1), 2, "281", Submission::Correct);
+        answers.record(day!(2), 1, "8");
+        Progress::from_sources(|day| day <= 2, &answers, &Timings::default())
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+    }
+
+    #[test]
+    fn updates_existing_progress() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## Progress").count(), 1);
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- progress table --->",
+            "## Progress",
+            "",
+            "**⭐ 2 / 50**",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | ★ | ★ |",
+            "| [Day 2](./src/bin/02.rs) | ☆ | · |",
+            "",
+            "★ star, ☆ answered but not submitted, ✖ rejected, · unsolved",
+            "<!--- progress table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
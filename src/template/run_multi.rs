@@ -1,4 +1,4 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, io};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
@@ -58,6 +58,15 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read output of child process."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
@@ -77,9 +86,21 @@ pub mod child_commands {
 
     /// Run the solution bin for a given day
     pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        // mirror `--time` flag to child invocations.
+        let bin_args: &[&str] = if is_timed { &["--time"] } else { &[] };
+        run_solution_with_args(day, is_release, bin_args).map(|(output, _)| output)
+    }
+
+    /// Run the solution bin for a given day, passing `bin_args` to the solution.
+    /// Returns the lines written to stdout and whether the solution exited successfully.
+    pub fn run_solution_with_args(
+        day: Day,
+        is_release: bool,
+        bin_args: &[&str],
+    ) -> Result<(Vec<String>, bool), Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok((vec![], true));
         }
 
         let day_padded = day.to_string();
@@ -89,10 +110,9 @@ pub mod child_commands {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
+        if !bin_args.is_empty() {
             args.push("--");
-            args.push("--time");
+            args.extend(bin_args);
         }
 
         // spawn child command with piped stdout/stderr.
@@ -122,9 +142,9 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok((output, status.success()))
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,8 +153,11 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            seed: None,
         };
 
+        timings.seed = output.iter().find_map(|l| parse_seed(l));
+
         output
             .iter()
             .filter_map(|l| {
@@ -163,6 +186,15 @@ pub mod child_commands {
         timings
     }
 
+    /// Parses the seed from the line `Seed: <seed> (<source>)` printed after the answers.
+    fn parse_seed(line: &str) -> Option<u64> {
+        let seed = line.split("Seed: ").nth(1)?;
+        seed.split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -173,7 +205,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -189,7 +221,7 @@ pub mod child_commands {
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -202,7 +234,7 @@ pub mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
         use super::parse_exec_time;
 
@@ -221,6 +253,20 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.seed, None);
+        }
+
+        #[test]
+        fn parses_seed() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 54 (10.5ms @ 95 samples)".into(),
+                    "Part 2: ✖        ".into(),
+                    "\x1b[3mSeed: 2023 (benchmark default)\x1b[0m".into(),
+                ],
+                day!(25),
+            );
+            assert_eq!(res.seed, Some(2023));
         }
 
         #[test]
@@ -1,24 +1,304 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
+use std::io::{self, stdout, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use crate::dot::Dot;
+use crate::error::Error;
+use crate::ocr;
+use crate::random;
+use crate::template::answers::{Answers, Submission};
+use crate::template::inputs::{read_inputs, Input};
+use crate::template::progress::Progress;
+use crate::template::readme_progress;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, Normalize, ANSI_ITALIC, ANSI_RESET};
+use crate::trace;
+
+/// What a solution part returns: `Option<T>`, or `Result<T, Error>` for a part that can explain
+/// why it has no answer.
+pub trait PartOutput {
+    type Answer: Display;
+
+    /// The answer, `None` if there is none, or the error that prevented one.
+    fn answer(&self) -> Result<Option<&Self::Answer>, &Error>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, &Error> {
+        Ok(self.as_ref())
+    }
+}
+
+impl<T: Display> PartOutput for Result<T, Error> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, &Error> {
+        self.as_ref().map(Some)
+    }
+}
+
+/// A function exporting the graph of an input, see [`write_graphs`].
+pub type GraphExport = fn(&str) -> Result<Dot, Error>;
+
+/// What a graph export returns: a [`Dot`], or `Result<Dot, Error>` for an export that can fail
+/// on invalid input.
+pub trait GraphOutput {
+    fn into_graph(self) -> Result<Dot, Error>;
+}
+
+impl GraphOutput for Dot {
+    fn into_graph(self) -> Result<Dot, Error> {
+        Ok(self)
+    }
+}
+
+impl GraphOutput for Result<Dot, Error> {
+    fn into_graph(self) -> Result<Dot, Error> {
+        self
+    }
+}
+
+/// Show why a part failed on stderr, keeping it apart from the answers on stdout.
+fn print_error<R: PartOutput>(result: &R, day: Day) {
+    if let Err(e) = result.answer() {
+        eprintln!("{}", e.clone().with_day(day));
+    }
+}
+
+/// Read the inputs to run for a day. Benchmarks only use the primary input.
+/// An input passed with `--input <path>`, or `--input -` for stdin, replaces the inputs of the day.
+/// Prints the error and returns `None` if the day has no input.
+pub fn inputs_to_run(day: Day) -> Option<Vec<Input>> {
+    if let Some(path) = arg_value("--input", "cargo solve 1 --input <path|->") {
+        return match read_input_arg(&path) {
+            Ok(input) => Some(vec![input]),
+            Err(e) => {
+                eprintln!("Error: could not read input \"{path}\": {e}");
+                None
+            }
+        };
+    }
+
+    let mut inputs = match read_inputs(day) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return None;
+        }
+    };
+    if env::args().any(|x| x == "--time") {
+        inputs.truncate(1);
+    }
+    Some(inputs)
+}
+
+/// Read an input given on the command line. It is never used to store or submit answers.
+fn read_input_arg(path: &str) -> io::Result<Input> {
+    let (name, data) = if path == "-" {
+        let mut data = String::new();
+        io::stdin().read_to_string(&mut data)?;
+        ("stdin".to_string(), data)
+    } else {
+        (path.to_string(), fs::read_to_string(path)?)
+    };
+
+    Ok(Input {
+        name,
+        data: Normalize::default().apply(&data),
+        expected: [None, None],
+        is_primary: false,
+    })
+}
+
+/// Returns the value following `name` in the command-line arguments, exiting if it is missing.
+fn arg_value(name: &str, format: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == name)? + 1;
+
+    let Some(value) = args.get(index) else {
+        eprintln!("Unexpected command-line input. Format: {format}");
+        process::exit(1);
+    };
+
+    Some(value.clone())
+}
+
+/// Returns the part selected with `--part`, if any.
+fn selected_part() -> Option<u8> {
+    let format = "cargo solve 1 --part <1|2>";
+    let part = arg_value("--part", format)?;
+    match part.parse::<u8>() {
+        Ok(part @ (1 | 2)) => Some(part),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: {format}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether `--graph` was passed, to export the graph of each input instead of solving.
+pub fn graph_requested() -> bool {
+    env::args().any(|x| x == "--graph")
+}
+
+/// Write the graph of each input to `data/graph/`, for a day that exports one.
+/// Returns `false` if the day has no export or a file could not be written.
+pub fn write_graphs(export: Option<GraphExport>, inputs: &[Input], day: Day) -> bool {
+    let Some(export) = export else {
+        eprintln!("Day {day} does not export a graph. Add one with `solution!({day}, graph = <function>)`.");
+        return false;
+    };
+
+    let mut passed = true;
+    for input in inputs {
+        let name = (!input.is_primary).then_some(input.name.as_str());
+        let dot = match export(&input.data) {
+            Ok(dot) => dot,
+            Err(e) => {
+                eprintln!("{}", e.with_day(day));
+                passed = false;
+                continue;
+            }
+        };
+        match dot.write(day, name) {
+            Ok(path) => println!("Graph: {}", path.display()),
+            Err(e) => {
+                eprintln!(
+                    "Error: could not write the graph of input {}: {e}",
+                    input.name
+                );
+                passed = false;
+            }
+        }
+    }
+    passed
+}
+
+/// Label the output of an input when a day has more than one.
+pub fn print_input_header(input: &Input, input_count: usize) {
+    if input_count > 1 {
+        println!("{ANSI_ITALIC}Input {}{ANSI_RESET}", input.name);
+    }
+}
+
+/// Print the seed of the run if the solution of this day drew random numbers, so that it can be
+/// replayed with `--seed`. `cargo time` stores it with the timings.
+pub fn print_seed() {
+    if let Some(seed) = random::take_used_seed() {
+        println!("{ANSI_ITALIC}Seed: {seed}{ANSI_RESET}");
+    }
+}
+
+/// Run a solution part against one of the inputs of a day and compare the result with the
+/// expected answer, if known. Answers are only stored and submitted for the primary input.
+/// Returns `false` if the part panicked or produced a wrong answer.
+pub fn run_input_part<R: PartOutput>(
+    func: impl Fn(&str) -> R,
+    input: &Input,
+    day: Day,
+    part: u8,
+) -> bool {
+    if selected_part().is_some_and(|selected| selected != part) {
+        return true;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    let is_verify = env::args().any(|x| x == "--verify");
+
+    // catch panics so that a solution failing on one input does not hide the results of the others.
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        let (result, duration, samples) = run_timed(&func, input.data.as_str(), |result| {
+            print_result(&result.answer().ok().flatten(), &part_str, "");
+        });
+        print_result(
+            &result.answer().ok().flatten(),
+            &part_str,
+            &format_duration(&duration, samples),
+        );
+        print_error(&result, day);
+        result
+    }));
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let Ok(result) = outcome else {
+        print!("\r");
+        println!("{part_str}: ✖ panicked");
+        return false;
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let answer = result.answer().ok().flatten().map(answer_text);
+    let passed = match (answer.as_deref(), input.expected(part)) {
+        (Some(answer), Some(expected)) if answer == expected => {
+            if is_verify {
+                println!("  ✔ matches expected answer");
+            }
+            true
+        }
+        (_, Some(expected)) => {
+            println!("  ✖ expected {ANSI_BOLD}{expected}{ANSI_RESET}");
+            false
+        }
+        (_, None) => {
+            if is_verify {
+                println!("  {ANSI_ITALIC}no expected answer{ANSI_RESET}");
+            }
+            true
+        }
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    // embedded inputs are meant for benchmarking on other machines, where there are no answers to store.
+    if input.is_primary && !is_verify && !cfg!(feature = "embed-inputs") {
+        if let Ok(Some(answer)) = result.answer() {
+            record_answer(answer, day, part);
+        }
+    }
+
+    passed
+}
+
+/// Store the answer in `data/answers.json`, submitting it first if requested.
+/// A submission also refreshes the progress table in the readme.
+fn record_answer<T: Display>(result: T, day: Day, part: u8) {
+    let answer = answer_text(&result);
+    let mut answers = Answers::read_from_file();
+
+    if let Some(stored) = answers.get(day, part) {
+        if stored.value == answer && stored.submission == Submission::Incorrect {
+            println!("{ANSI_ITALIC}This answer was previously rejected.{ANSI_RESET}");
+        }
+    }
+
+    let changed = answers.record(day, part, &answer);
+
+    let Some(output) = submit_result(&answer, day, part) else {
+        if changed {
+            if let Err(e) = answers.store_file() {
+                eprintln!("Failed to store answer: {e}");
+            }
+        }
+        return;
+    };
+
+    let submission = match output {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => {
+            Submission::from_aoc_output(&String::from_utf8_lossy(&output.stdout))
+        }
+        Err(_) => Submission::Pending,
+    };
+    answers.record_submission(day, part, &answer, submission);
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answer: {e}");
+    }
+
+    if let Err(e) = readme_progress::update(&Progress::collect()) {
+        eprintln!("Failed to store updated progress: {e}");
     }
 }
 
@@ -58,23 +338,21 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
-    }
+    // Only the first run is traced, rather than every iteration of the benchmark
+    trace::muted(|| {
+        for _ in 0..bench_iterations {
+            // need a clone here to make the borrow checker happy.
+            let cloned = input.clone();
+            let timer = Instant::now();
+            black_box(func(black_box(cloned)));
+            timers.push(timer.elapsed());
+        }
+    });
 
     (
         #[allow(clippy::cast_possible_truncation)]
@@ -99,12 +377,32 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+/// The answer to store and submit: the letters drawn by an answer in pixel art, or the answer
+/// as it is printed otherwise.
+fn answer_text<T: Display>(result: &T) -> String {
+    let text = result.to_string();
+    match text.contains('\n') {
+        true => ocr::decode(&text).unwrap_or(text),
+        false => text,
+    }
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
+            let text = result.to_string();
+            if let Some(letters) = text.contains('\n').then(|| ocr::decode(&text)).flatten() {
+                let str = format!("{part}: {ANSI_BOLD}{letters}{ANSI_RESET}{duration_str} ▼");
+                if is_intermediate_result {
+                    print!("{str}");
+                } else {
+                    print!("\r");
+                    println!("{str}");
+                    println!("{result}");
+                }
+            } else if text.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -137,8 +435,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn submit_result(
+    answer: &str,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
@@ -170,5 +468,18 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, answer))
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::answer_text;
+
+    #[test]
+    fn decodes_letter_answers() {
+        let art = "\n####.#..#\n#....#..#\n###..####\n#....#..#\n#....#..#\n#....#..#";
+        assert_eq!(answer_text(&art), "FH");
+        assert_eq!(answer_text(&"a\nb"), "a\nb");
+        assert_eq!(answer_text(&42), "42");
+    }
 }
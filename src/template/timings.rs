@@ -12,6 +12,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// The seed of a solution that draws random numbers, to replay the benchmark.
+    pub seed: Option<u64>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -58,7 +60,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -140,6 +142,15 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        // stored as a string, as JSON numbers can't hold every u64
+        map.insert(
+            "seed".into(),
+            match value.seed {
+                Some(seed) => JsonValue::String(seed.to_string()),
+                None => JsonValue::Null,
+            },
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -173,18 +184,29 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before seeds were recorded have no key
+        let seed = match json.get("seed") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or("Expected timing.seed to be null or a numeric string.")?,
+            ),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            seed,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use crate::day;
 
@@ -198,18 +220,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    seed: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    seed: Some(2023),
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    seed: None,
                 },
             ],
         }
@@ -228,6 +253,14 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.seed, None);
+        }
+
+        #[test]
+        fn handles_seeds() {
+            let json = r#"{ "data": [{ "day": "25", "part_1": "1s", "part_2": null, "total_nanos": 1000000000, "seed": "18446744073709551615" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].seed, Some(u64::MAX));
         }
 
         #[test]
@@ -289,10 +322,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    seed: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +337,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    seed: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,10 +352,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    seed: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -341,6 +377,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    seed: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +398,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    seed: None,
                 }],
             };
             let merged = timings.merge(&other);
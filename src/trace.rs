@@ -0,0 +1,256 @@
+//! Debug tracing for solutions, switched on from the command line.
+//!
+//! Messages are written to stderr, so they never mix with the answers on stdout that `cargo all`
+//! and `cargo time` parse. Each message has a [`Level`] and a target: the day for a solution
+//! binary (`day20`), or the module for the library (`maze`).
+//!
+//! Tracing is only compiled in with the `trace` feature, which `cargo solve` enables when it is
+//! given `--trace`. Without it the macros compile to nothing, so benchmarks are unaffected:
+//!
+//! ```sh
+//! cargo solve 25 --trace              # debug and above, for everything
+//! cargo solve 25 --trace=info         # info and above
+//! cargo solve 25 --trace=warn,day25=trace,graph=debug
+//! ```
+//!
+//! ```
+//! # use advent_of_code::{trace, trace_enabled};
+//! # let cuts = 3;
+//! trace!(Debug, "found a cut of {cuts} edges");
+//! if trace_enabled!(Trace) {
+//!     // Expensive checks that only run when they would be reported
+//! }
+//! ```
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// Whether tracing was compiled in. When false, the macros are optimised away entirely.
+pub const COMPILED: bool = cfg!(feature = "trace");
+
+/// How important a message is, from the most to the least.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(ParseFilterError(s.to_string())),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseFilterError(String);
+
+impl std::error::Error for ParseFilterError {}
+
+impl Display for ParseFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid trace level \"{}\", expected one of error, warn, info, debug or trace",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Which messages to show: a default level, and levels for particular targets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// The most detailed level shown for a target, or `None` if it is silent.
+    pub fn max_level(&self, target: &str) -> Option<Level> {
+        self.targets
+            .iter()
+            .find(|(t, _)| t == target)
+            .map(|&(_, level)| level)
+            .or(self.default)
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        self.max_level(target).is_some_and(|max| level <= max)
+    }
+}
+
+/// Parses a comma separated list of directives, each either a level for every target, or a
+/// `target=level` pair.
+impl FromStr for Filter {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    filter.targets.push((target.to_string(), level.parse()?));
+                }
+                None => filter.default = Some(directive.parse()?),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// The filter given on the command line: `--trace` shows debug messages and above, and
+/// `--trace=<filter>` is parsed as a [`Filter`]. Without either, nothing is shown.
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+    FILTER.get_or_init(|| {
+        let Some(arg) = std::env::args().find(|a| a == "--trace" || a.starts_with("--trace="))
+        else {
+            return Filter::default();
+        };
+        match arg.strip_prefix("--trace=") {
+            None => Filter {
+                default: Some(Level::Debug),
+                targets: vec![],
+            },
+            Some(spec) => spec.parse().unwrap_or_else(|e| {
+                eprintln!(
+                    "Error: {e}. Format: cargo solve 1 --trace[=<level>|<target>=<level>,...]"
+                );
+                std::process::exit(1);
+            }),
+        }
+    })
+}
+
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Runs `f` without tracing, such as while it is being benchmarked.
+pub fn muted<T>(f: impl FnOnce() -> T) -> T {
+    let was_muted = MUTED.swap(true, Ordering::Relaxed);
+    let result = f();
+    MUTED.store(was_muted, Ordering::Relaxed);
+    result
+}
+
+/// The target of a module: the day for a solution binary, such as `day20` for the binary `20`,
+/// or the first module below the library, such as `maze`.
+pub fn target(module_path: &'static str) -> Cow<'static, str> {
+    let first = module_path.split("::").next().unwrap_or_default();
+    let day = first.trim_start_matches('_');
+    if !day.is_empty() && day.chars().all(|c| c.is_ascii_digit()) {
+        return Cow::Owned(format!("day{day}"));
+    }
+    match module_path.strip_prefix("advent_of_code::") {
+        Some(rest) => Cow::Borrowed(rest.split("::").next().unwrap_or_default()),
+        None => Cow::Borrowed(first),
+    }
+}
+
+/// Whether a message at `level` for `target` would be shown. Use the [`trace_enabled!`]
+/// macro rather than calling this directly, so that the check compiles away without tracing.
+///
+/// [`trace_enabled!`]: crate::trace_enabled
+pub fn enabled(level: Level, target: &str) -> bool {
+    COMPILED && !MUTED.load(Ordering::Relaxed) && filter().enabled(level, target)
+}
+
+/// Writes a message to stderr. Use the [`trace!`](crate::trace) macro instead.
+pub fn emit(level: Level, target: &str, message: fmt::Arguments) {
+    eprintln!("{ANSI_ITALIC}[{level} {target}]{ANSI_RESET} {message}");
+}
+
+/// Writes a formatted message to stderr if tracing is on for its level and the calling day or
+/// module. The level is one of the [`Level`](crate::trace::Level) variants, and the target can
+/// be given explicitly:
+///
+/// ```
+/// # use advent_of_code::trace;
+/// trace!(Info, "starting");
+/// trace!(target: "karger", Trace, "contracting {} into {}", 4, 2);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    (target: $target:expr, $level:ident, $($arg:tt)+) => {
+        if $crate::trace::COMPILED
+            && $crate::trace::enabled($crate::trace::Level::$level, &$target)
+        {
+            $crate::trace::emit($crate::trace::Level::$level, &$target, format_args!($($arg)+));
+        }
+    };
+    ($level:ident, $($arg:tt)+) => {
+        $crate::trace!(target: $crate::trace::target(module_path!()), $level, $($arg)+)
+    };
+}
+
+/// Whether tracing is on for a level and the calling day or module, to guard work that is only
+/// needed for tracing, such as consistency checks.
+#[macro_export]
+macro_rules! trace_enabled {
+    ($level:ident) => {
+        $crate::trace::COMPILED
+            && $crate::trace::enabled(
+                $crate::trace::Level::$level,
+                &$crate::trace::target(module_path!()),
+            )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "warn, day25=trace,maze=error".parse().unwrap();
+        assert!(filter.enabled(Level::Trace, "day25"));
+        assert!(filter.enabled(Level::Warn, "day20"));
+        assert!(!filter.enabled(Level::Info, "day20"));
+        assert!(!filter.enabled(Level::Warn, "maze"));
+
+        let filter: Filter = "day1=info".parse().unwrap();
+        assert_eq!(filter.max_level("day1"), Some(Level::Info));
+        assert_eq!(filter.max_level("day2"), None);
+
+        assert!("verbose".parse::<Filter>().is_err());
+        assert!("day1=".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_target() {
+        assert_eq!(target("20"), "day20");
+        assert_eq!(target("20::tests"), "day20");
+        assert_eq!(target("advent_of_code::maze"), "maze");
+        assert_eq!(target("advent_of_code::template::runner"), "template");
+        assert_eq!(target("other"), "other");
+    }
+}
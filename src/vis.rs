@@ -0,0 +1,551 @@
+//! Visualising grid puzzles, in the terminal or as images.
+//!
+//! A [`Frame`] is a grid of coloured symbols, usually made from a puzzle [`Grid`] with overlays
+//! painted on top for paths, visited sets or highlighted cells. It renders to ANSI text for the
+//! terminal, or to PPM and PNG images. A [`Recorder`] writes a numbered sequence of frames to
+//! `data/vis/NN/`, which can be stitched into an animation with a tool such as `ffmpeg`.
+//!
+//! ```
+//! # use advent_of_code::grid::Grid;
+//! # use advent_of_code::vis::{Cell, Color, Frame};
+//! let maze = Grid::parse("#.#\n...\n#.#", |c| c);
+//! let mut frame = Frame::from_grid(&maze, |&c| match c {
+//!     '#' => Cell::new('#').fg(Color::GREY),
+//!     _ => Cell::new(' '),
+//! });
+//! frame.path([(1, 0), (1, 1), (2, 1)], Color::YELLOW);
+//! frame.highlight([(0, 1)], Color::RED);
+//! assert_eq!(frame.to_string(), "#↓#\n →>\n# #\n");
+//! ```
+//!
+//! Recording is switched on by passing `--vis` to `cargo solve`, and [`Recorder::new`] returns
+//! `None` otherwise, so that a solution only builds frames when they are wanted:
+//!
+//! ```no_run
+//! # use advent_of_code::template::Day;
+//! # use advent_of_code::vis::{Frame, Recorder};
+//! # let frames: Vec<Frame> = vec![];
+//! if let Some(mut recorder) = Recorder::new(Day::new(21).unwrap(), "part_one") {
+//!     for frame in &frames {
+//!         recorder.record(frame).unwrap();
+//!     }
+//! }
+//! ```
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Write as _};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::grid::Grid;
+use crate::template::Day;
+
+/// A colour, as red, green and blue components.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GREY: Color = Color::rgb(128, 128, 128);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(80, 200, 80);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const YELLOW: Color = Color::rgb(230, 200, 40);
+    pub const CYAN: Color = Color::rgb(42, 190, 180);
+    pub const MAGENTA: Color = Color::rgb(211, 54, 130);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// A colour from a fixed palette, for telling apart a handful of categories. Indexes past the
+    /// end of the palette wrap around.
+    pub fn indexed(index: usize) -> Self {
+        const PALETTE: [Color; 7] = [
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::YELLOW,
+            Color::CYAN,
+            Color::MAGENTA,
+            Color::WHITE,
+        ];
+        PALETTE[index % PALETTE.len()]
+    }
+}
+
+/// One position of a [`Frame`]: a symbol with optional foreground and background colours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// The colour of the cell in an image, where symbols can't be drawn: the background if it
+    /// has one, otherwise the foreground of a visible symbol.
+    fn pixel(&self) -> Color {
+        match (self.bg, self.fg) {
+            (Some(bg), _) => bg,
+            (None, Some(fg)) if !self.symbol.is_whitespace() => fg,
+            (None, _) if !self.symbol.is_whitespace() && self.symbol != '.' => Color::GREY,
+            _ => Color::BLACK,
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new(' ')
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A grid of cells to render.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+}
+
+impl Frame {
+    /// A blank frame.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::new(width, height, Cell::default()),
+        }
+    }
+
+    /// A frame with the cells given row by row.
+    ///
+    /// # Panics
+    /// Panics if the number of cells is not a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<Cell>) -> Self {
+        Self {
+            cells: Grid::from_vec(width, cells),
+        }
+    }
+
+    /// A frame showing each cell of a puzzle grid.
+    pub fn from_grid<T>(grid: &Grid<T>, cell: impl FnMut(&T) -> Cell) -> Self {
+        Self {
+            cells: grid.map(cell),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
+        self.cells.get_mut(x as isize, y as isize)
+    }
+
+    /// Changes the cells at each of the positions. Positions outside the frame are ignored, so
+    /// that an overlay can be painted without clipping it first.
+    pub fn paint(
+        &mut self,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+        mut f: impl FnMut(&mut Cell),
+    ) {
+        for (x, y) in positions {
+            if let Some(cell) = self.get_mut(x, y) {
+                f(cell);
+            }
+        }
+    }
+
+    /// Colours the background of each position, keeping its symbol.
+    pub fn highlight(&mut self, positions: impl IntoIterator<Item = (usize, usize)>, color: Color) {
+        self.paint(positions, |cell| cell.bg = Some(color));
+    }
+
+    /// Replaces the symbol at each position, such as an `O` for every visited cell.
+    pub fn mark(
+        &mut self,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+        symbol: char,
+        color: Color,
+    ) {
+        self.paint(positions, |cell| {
+            *cell = Cell {
+                symbol,
+                fg: Some(color),
+                ..*cell
+            }
+        });
+    }
+
+    /// Draws a path through consecutive positions, with each step shown as an arrow pointing
+    /// to the next position and the end of the path as `>`.
+    pub fn path(&mut self, positions: impl IntoIterator<Item = (usize, usize)>, color: Color) {
+        let positions: Vec<(usize, usize)> = positions.into_iter().collect();
+        let arrows = positions.windows(2).map(|step| {
+            let ((x1, y1), (x2, y2)) = (step[0], step[1]);
+            let symbol = match (x2.cmp(&x1), y2.cmp(&y1)) {
+                (Ordering::Greater, _) => '→',
+                (Ordering::Less, _) => '←',
+                (_, Ordering::Greater) => '↓',
+                (_, Ordering::Less) => '↑',
+                _ => '•',
+            };
+            (step[0], symbol)
+        });
+        for ((x, y), symbol) in arrows.chain(positions.last().map(|&end| (end, '>'))) {
+            self.mark([(x, y)], symbol, color);
+        }
+    }
+
+    /// The frame as text with ANSI colour codes, for printing to a terminal.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.rows() {
+            let mut style = (None, None);
+            for cell in row {
+                if (cell.fg, cell.bg) != style {
+                    style = (cell.fg, cell.bg);
+                    out.push_str("\x1b[0m");
+                    if let Some(c) = cell.fg {
+                        let _ = write!(out, "\x1b[38;2;{};{};{}m", c.r, c.g, c.b);
+                    }
+                    if let Some(c) = cell.bg {
+                        let _ = write!(out, "\x1b[48;2;{};{};{}m", c.r, c.g, c.b);
+                    }
+                }
+                out.push(cell.symbol);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+
+    /// The colours of the image, with each cell drawn as a `scale` by `scale` square.
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.width() * self.height() * scale * scale * 3);
+        for row in self.cells.rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| {
+                    let c = cell.pixel();
+                    [c.r, c.g, c.b].repeat(scale)
+                })
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+
+    /// The frame as a binary PPM image, with each cell drawn as a `scale` by `scale` square.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let mut image = format!(
+            "P6\n{} {}\n255\n",
+            self.width() * scale,
+            self.height() * scale
+        )
+        .into_bytes();
+        image.extend(self.pixels(scale));
+        image
+    }
+
+    /// The frame as a PNG image, with each cell drawn as a `scale` by `scale` square.
+    pub fn to_png(&self, scale: usize) -> Vec<u8> {
+        png::encode(
+            (self.width() * scale) as u32,
+            (self.height() * scale) as u32,
+            &self.pixels(scale),
+        )
+    }
+}
+
+/// The symbols of the frame, without colour.
+impl Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.rows() {
+            for cell in row {
+                f.write_char(cell.symbol)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// A minimal PNG encoder: 8-bit RGB, with the image data stored uncompressed. The files are
+/// larger than they need to be, but any viewer or `ffmpeg` can read them.
+mod png {
+    pub fn encode(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        // Bit depth 8, colour type RGB, default compression, filter and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every scanline starts with its filter type, which is none
+        let stride = width as usize * 3;
+        let mut scanlines = Vec::with_capacity(rgb.len() + height as usize);
+        for line in rgb.chunks(stride.max(1)).take(height as usize) {
+            scanlines.push(0);
+            scanlines.extend_from_slice(line);
+        }
+
+        let mut image = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut image, b"IHDR", &header);
+        chunk(&mut image, b"IDAT", &zlib_stored(&scanlines));
+        chunk(&mut image, b"IEND", &[]);
+        image
+    }
+
+    fn chunk(image: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        image.extend((data.len() as u32).to_be_bytes());
+        let start = image.len();
+        image.extend(kind);
+        image.extend(data);
+        let crc = crc32(&image[start..]);
+        image.extend(crc.to_be_bytes());
+    }
+
+    /// Wraps the data in a zlib stream of uncompressed deflate blocks.
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut stream = vec![0x78, 0x01];
+        let mut blocks = data.chunks(u16::MAX as usize).peekable();
+        if blocks.peek().is_none() {
+            stream.extend([1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(block) = blocks.next() {
+            let last = blocks.peek().is_none();
+            let len = block.len() as u16;
+            stream.push(last as u8);
+            stream.extend(len.to_le_bytes());
+            stream.extend((!len).to_le_bytes());
+            stream.extend(block);
+        }
+        stream.extend(adler32(data).to_be_bytes());
+        stream
+    }
+
+    fn crc32(data: &[u8]) -> u32 {
+        !data.iter().fold(!0, |crc, &byte| {
+            (0..8).fold(crc ^ byte as u32, |crc, _| {
+                (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg())
+            })
+        })
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        let (a, b) = data.iter().fold((1, 0), |(a, b), &byte| {
+            let a = (a + byte as u32) % 65521;
+            (a, (b + a) % 65521)
+        });
+        (b << 16) | a
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_checksums() {
+            assert_eq!(crc32(b"IEND"), 0xae426082);
+            assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether `--vis` was passed on the command line.
+pub fn enabled() -> bool {
+    std::env::args().any(|x| x == "--vis")
+}
+
+/// The image format that a [`Recorder`] writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Ppm,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Ppm => "ppm",
+        }
+    }
+}
+
+/// Writes a numbered sequence of frames as images, such as `data/vis/21/part_one_0001.png`.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    name: String,
+    format: ImageFormat,
+    scale: usize,
+    frames: usize,
+}
+
+impl Recorder {
+    /// A recorder writing to `data/vis/NN/` if `--vis` was passed, or `None` otherwise.
+    pub fn new(day: Day, name: &str) -> Option<Self> {
+        enabled().then(|| Self::in_dir(format!("./data/vis/{day}"), name))
+    }
+
+    /// A recorder writing to the given directory, whether or not `--vis` was passed.
+    pub fn in_dir(dir: impl Into<PathBuf>, name: &str) -> Self {
+        Self {
+            dir: dir.into(),
+            name: name.to_string(),
+            format: ImageFormat::Png,
+            scale: 4,
+            frames: 0,
+        }
+    }
+
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The size in pixels of the square drawn for each cell.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The number of frames recorded so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Writes the next frame, returning the path of the image. The first frame also removes the
+    /// images left by an earlier run under the same name.
+    pub fn record(&mut self, frame: &Frame) -> io::Result<PathBuf> {
+        if self.frames == 0 {
+            self.clear()?;
+        }
+        self.frames += 1;
+
+        let path = self.dir.join(format!(
+            "{}_{:04}.{}",
+            self.name,
+            self.frames,
+            self.format.extension()
+        ));
+        let image = match self.format {
+            ImageFormat::Png => frame.to_png(self.scale),
+            ImageFormat::Ppm => frame.to_ppm(self.scale),
+        };
+        fs::write(&path, image)?;
+        Ok(path)
+    }
+
+    fn clear(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let prefix = format!("{}_", self.name);
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let is_frame = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                n.strip_prefix(&prefix)
+                    .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+            });
+            if is_frame && path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Frame {
+        let grid = Grid::parse("#.\n..", |c| c == '#');
+        Frame::from_grid(&grid, |&rock| match rock {
+            true => Cell::new('#').fg(Color::WHITE),
+            false => Cell::new('.'),
+        })
+    }
+
+    #[test]
+    fn test_overlays() {
+        let mut frame = sample();
+        frame.mark([(1, 1), (5, 5)], 'O', Color::GREEN);
+        assert_eq!(frame.to_string(), "#.\n.O\n");
+
+        frame.path([(0, 1), (1, 1), (1, 0)], Color::RED);
+        assert_eq!(frame.to_string(), "#>\n→↑\n");
+
+        frame.highlight([(0, 0)], Color::BLUE);
+        assert_eq!(frame.get_mut(0, 0).unwrap().symbol, '#');
+        assert_eq!(frame.get_mut(0, 0).unwrap().bg, Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let frame = sample();
+        assert_eq!(
+            frame.to_ansi(),
+            "\x1b[0m\x1b[38;2;255;255;255m#\x1b[0m.\x1b[0m\n..\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_images() {
+        let frame = sample();
+        let ppm = frame.to_ppm(1);
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(&ppm[11..], &[255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(frame.to_ppm(3).len(), 11 + 6 * 6 * 3);
+
+        let png = frame.to_png(2);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x04\0\0\0\x04"));
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn test_recorder() {
+        let dir = std::env::temp_dir().join(format!("aoc_vis_{}", std::process::id()));
+        let mut recorder = Recorder::in_dir(&dir, "test").format(ImageFormat::Ppm);
+        recorder.record(&sample()).unwrap();
+        let second = recorder.record(&sample()).unwrap();
+        assert_eq!(second.file_name().unwrap(), "test_0002.ppm");
+
+        // A new recording replaces the frames of the last one
+        let mut recorder = Recorder::in_dir(&dir, "test").format(ImageFormat::Ppm);
+        recorder.record(&sample()).unwrap();
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}